@@ -0,0 +1,59 @@
+use std::fmt;
+
+/// The reason the lexer stopped
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LexErrorKind {
+    /// A character that cannot start any token
+    UnexpectedCharacter(char),
+    /// A string literal without its closing quote
+    UnterminatedString,
+    /// A template literal without its closing backtick
+    UnterminatedTemplate,
+    /// A regular expression literal without its closing slash
+    UnterminatedRegExp,
+    /// A line terminator inside a string literal
+    UnexpectedLineTerminator,
+    /// A `/*` comment without its closing `*/`
+    UnterminatedComment,
+    /// `\x` or `\u` not followed by enough hexadecimal digits
+    InvalidHexEscape,
+    /// `0x` not followed by a hexadecimal digit
+    InvalidHexNumber,
+    /// A legacy octal escape such as `\1` or `\01`
+    OctalLiteral,
+    /// A number without any digits, such as `.e1`
+    InvalidNumber,
+}
+
+/// An error reported by the lexer, retrieved with [`Lexer::error`](crate::lexer::Lexer::error)
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl fmt::Display for LexErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedCharacter(c) => write!(f, "Unexpected character `{c}`"),
+            Self::UnterminatedString => write!(f, "Unterminated string literal"),
+            Self::UnterminatedTemplate => write!(f, "Unterminated template literal"),
+            Self::UnterminatedRegExp => write!(f, "Unterminated regular expression literal"),
+            Self::UnexpectedLineTerminator => write!(f, "Unexpected line terminator in string"),
+            Self::UnterminatedComment => write!(f, "Unterminated comment"),
+            Self::InvalidHexEscape => write!(f, "Invalid hexadecimal escape sequence"),
+            Self::InvalidHexNumber => write!(f, "Expected hexadecimal number"),
+            Self::OctalLiteral => write!(f, "Octal escape sequences are not allowed"),
+            Self::InvalidNumber => write!(f, "Expected decimal number"),
+        }
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.start)
+    }
+}
+
+impl std::error::Error for LexError {}
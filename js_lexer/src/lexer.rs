@@ -1,49 +1,74 @@
+use crate::error::{LexError, LexErrorKind};
 use crate::statefn::StateFn;
+use crate::statefn_impl::lex_regex;
 use crate::token::{Token, Type};
-use std::sync::{mpsc, Arc};
-
-/// Create a token stream from the input string
-///
-/// * `input`: the input string to tokenize
-pub fn token_stream(input: &str) -> mpsc::Receiver<Token> {
-    let (tx, rx) = mpsc::channel();
-    let mut l = Lexer {
-        input: Arc::from(input),
-        start: 0,
-        pos: 0,
-        initial_state: StateFn::default(),
-        sender: tx,
-    };
-    std::thread::spawn(move || l.run());
-    rx
-}
+use std::collections::VecDeque;
 
 /// Lexer context
-pub struct Lexer {
-    input: Arc<str>,
+///
+/// The lexer is driven on demand: every call to [`Iterator::next`] runs state functions
+/// until at least one token has been emitted.
+pub struct Lexer<'a> {
+    input: &'a str,
     start: usize,
     pos: usize,
-    initial_state: StateFn,
-    sender: mpsc::Sender<Token>,
-    // error: Option<String>,
+    /// The next state function to run, `None` once the input is exhausted
+    state: Option<StateFn>,
+    /// Tokens emitted by the state functions but not yet consumed
+    tokens: VecDeque<Token>,
+    /// The error that stopped the lexer, if any
+    error: Option<LexError>,
+    /// A line terminator has been seen since the last token
+    on_new_line: bool,
+    /// Number of `{` not closed yet, including the `${` of template substitutions
+    brace_depth: usize,
+    /// The brace depth inside each open template substitution, innermost last
+    template_depths: Vec<usize>,
 }
 
-impl Lexer {
-    pub fn run(&mut self) {
-        let mut f = self.initial_state.clone();
-
-        // Run the state functions until there are no more state functions to run
-        // This pattern can decouple the lexer from the state functions and make it easier to
-        // extend the lexer with new state functions.
-        while let Some(next_f) = f.call(self) {
-            f = next_f;
+impl<'a> Lexer<'a> {
+    /// Create a lexer over the input string
+    ///
+    /// * `input`: the input string to tokenize
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            start: 0,
+            pos: 0,
+            state: Some(StateFn::default()),
+            tokens: VecDeque::new(),
+            error: None,
+            on_new_line: false,
+            brace_depth: 0,
+            template_depths: vec![],
         }
     }
 
+    /// The error that stopped the lexer, if any
+    ///
+    /// It is available once the [`Type::LexerError`] token has been returned.
+    pub fn error(&self) -> Option<&LexError> {
+        self.error.as_ref()
+    }
+
+    /// Lex a regular expression literal at `start`, where the parser expects an expression but
+    /// got the [`Type::Slash`] or [`Type::SlashEq`] token starting there
+    ///
+    /// Tokens already read past `start` are discarded, and lexing resumes after the literal.
+    /// The returned token is a [`Type::RegExp`] or a [`Type::LexerError`].
+    pub fn relex_regex(&mut self, start: usize) -> Token {
+        self.tokens.clear();
+        self.error = None;
+        self.start = start;
+        self.pos = start;
+        self.state = Some(StateFn::from(lex_regex));
+        self.next().unwrap_or_default()
+    }
+
     // The following methods are used by the state functions to interact with the lexer context:
 
-    pub(crate) fn current(&self) -> String {
-        self.input[self.start..self.pos].to_string()
+    pub(crate) fn current(&self) -> &'a str {
+        &self.input[self.start..self.pos]
     }
     /// Emit a token with the current value
     pub(crate) fn emit(&mut self, typ: Type) {
@@ -52,11 +77,33 @@ impl Lexer {
     }
     /// Send a token (without updating the start position)
     pub(crate) fn send(&mut self, typ: Type) {
-        let _ = self.sender.send(Token {
+        self.tokens.push_back(Token {
             typ,
             start: self.start,
             end: self.pos,
+            is_on_new_line: self.on_new_line,
         });
+        self.on_new_line = typ == Type::LineTerminator;
+    }
+    /// Flag the next token as being on a new line, for line terminators that are not emitted
+    pub(crate) fn mark_new_line(&mut self) {
+        self.on_new_line = true;
+    }
+    /// Track a `{`, or the `${` starting a template substitution
+    pub(crate) fn open_brace(&mut self, substitution: bool) {
+        self.brace_depth += 1;
+        if substitution {
+            self.template_depths.push(self.brace_depth);
+        }
+    }
+    /// Track a `}`, returning whether it closes a template substitution
+    pub(crate) fn close_brace(&mut self) -> bool {
+        let substitution = self.template_depths.last() == Some(&self.brace_depth);
+        if substitution {
+            self.template_depths.pop();
+        }
+        self.brace_depth = self.brace_depth.saturating_sub(1);
+        substitution
     }
     pub(crate) fn ignore(&mut self) {
         self.start = self.pos;
@@ -107,10 +154,220 @@ impl Lexer {
         }
         accepted
     }
-    pub(crate) fn error(&self, msg: &str) {
-        eprintln!("Lex error at position {}: {}", self.pos, msg);
+    /// Record the error and send a [`Type::LexerError`] token covering the offending text
+    pub(crate) fn fail(&mut self, kind: LexErrorKind) {
+        self.error = Some(LexError {
+            kind,
+            start: self.start,
+            end: self.pos,
+        });
+        self.send(Type::LexerError);
     }
     pub(crate) fn eof(&self) -> bool {
         self.pos >= self.input.len()
     }
 }
+
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        // Run the state functions until a token is available or there are no more state
+        // functions to run. This pattern can decouple the lexer from the state functions and
+        // make it easier to extend the lexer with new state functions.
+        loop {
+            if let Some(token) = self.tokens.pop_front() {
+                return Some(token);
+            }
+            let f = self.state.take()?;
+            self.state = f.call(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iterator_test() {
+        let mut lexer = Lexer::new("a = 1");
+        let first = lexer.next().unwrap();
+        assert_eq!((first.typ, first.start, first.end), (Type::Identifier, 0, 1));
+        let rest: Vec<_> = lexer.by_ref().map(|t| t.typ).collect();
+        assert_eq!(rest, [Type::Eq, Type::Decimal, Type::EOF]);
+        assert!(lexer.next().is_none());
+    }
+
+    #[test]
+    fn error_test() {
+        let cases = [
+            ("a = 'b", LexErrorKind::UnterminatedString, 4, 6),
+            ("'\\x4g'", LexErrorKind::InvalidHexEscape, 0, 4),
+            ("'\\07'", LexErrorKind::OctalLiteral, 0, 3),
+            ("a # b", LexErrorKind::UnexpectedCharacter('#'), 2, 3),
+            ("/* a", LexErrorKind::UnterminatedComment, 0, 4),
+            ("`a${b}", LexErrorKind::UnterminatedTemplate, 5, 6),
+        ];
+        for (source, kind, start, end) in cases {
+            let mut lexer = Lexer::new(source);
+            let last = lexer.by_ref().last().unwrap();
+            assert_eq!(last.typ, Type::LexerError, "{source}");
+            assert_eq!(lexer.error(), Some(&LexError { kind, start, end }), "{source}");
+        }
+
+        let mut lexer = Lexer::new("a // comment");
+        assert_eq!(lexer.by_ref().last().unwrap().typ, Type::EOF);
+        assert_eq!(lexer.error(), None);
+    }
+
+    #[test]
+    fn is_on_new_line_test() {
+        let tokens: Vec<_> = Lexer::new("a b\nc /* \n */ d /* */ e")
+            .filter(|t| t.typ != Type::LineTerminator)
+            .map(|t| t.is_on_new_line)
+            .collect();
+        assert_eq!(tokens, [false, false, true, true, false, false]);
+    }
+
+    #[test]
+    fn eq_test() {
+        let tokens: Vec<_> = Lexer::new("= == === => =>=")
+            .map(|t| t.typ)
+            .collect();
+        assert_eq!(
+            tokens,
+            [Type::Eq, Type::Eq2, Type::Eq3, Type::Arrow, Type::Arrow, Type::Eq, Type::EOF]
+        );
+    }
+
+    #[test]
+    fn bang_test() {
+        let tokens: Vec<_> = Lexer::new("! != !== !!").map(|t| t.typ).collect();
+        assert_eq!(
+            tokens,
+            [Type::Bang, Type::Neq, Type::Neq2, Type::Bang, Type::Bang, Type::EOF]
+        );
+    }
+
+    #[test]
+    fn string_escape_test() {
+        let source = r"'\n' '\\' '\0' 'a\'b'";
+        let tokens: Vec<_> = Lexer::new(source)
+            .map(|t| (t.typ, &source[t.start..t.end]))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Type::Str, r"'\n'"),
+                (Type::Str, r"'\\'"),
+                (Type::Str, r"'\0'"),
+                (Type::Str, r"'a\'b'"),
+                (Type::EOF, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn dot_test() {
+        let tokens: Vec<_> = Lexer::new("a.b ...c ..").map(|t| t.typ).collect();
+        assert_eq!(
+            tokens,
+            [
+                Type::Identifier,
+                Type::Dot,
+                Type::Identifier,
+                Type::Dot3,
+                Type::Identifier,
+                Type::Dot,
+                Type::Dot,
+                Type::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn template_test() {
+        let source = "`a` `b${c}d${ {e} }f\\`` }";
+        let tokens: Vec<_> = Lexer::new(source)
+            .map(|t| (t.typ, &source[t.start..t.end]))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Type::NoSubstitutionTemplate, "`a`"),
+                (Type::TemplateHead, "`b${"),
+                (Type::Identifier, "c"),
+                (Type::TemplateMiddle, "}d${"),
+                (Type::LCurly, "{"),
+                (Type::Identifier, "e"),
+                (Type::RCurly, "}"),
+                (Type::TemplateTail, "}f\\``"),
+                (Type::RCurly, "}"),
+                (Type::EOF, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn relex_regex_test() {
+        let source = "a = /[/]\\//gi.b";
+        let mut lexer = Lexer::new(source);
+        let slash = lexer.nth(2).unwrap();
+        assert_eq!(slash.typ, Type::Slash);
+        let regex = lexer.relex_regex(slash.start);
+        assert_eq!(regex.typ, Type::RegExp);
+        assert_eq!(&source[regex.start..regex.end], "/[/]\\//gi");
+        let rest: Vec<_> = lexer.map(|t| t.typ).collect();
+        assert_eq!(rest, [Type::Dot, Type::Identifier, Type::EOF]);
+
+        let mut lexer = Lexer::new("/a\n/");
+        assert_eq!(lexer.relex_regex(0).typ, Type::LexerError);
+        assert_eq!(
+            lexer.error().map(|error| error.kind),
+            Some(LexErrorKind::UnterminatedRegExp)
+        );
+    }
+
+    #[test]
+    fn question_test() {
+        let tokens: Vec<_> = Lexer::new("? ?? ??= ?. a?.5:b").map(|t| t.typ).collect();
+        assert_eq!(
+            tokens,
+            [
+                Type::Question,
+                Type::Question2,
+                Type::Question2Eq,
+                Type::QuestionDot,
+                Type::Identifier,
+                Type::Question,
+                Type::Dot,
+                Type::Decimal,
+                Type::Colon,
+                Type::Identifier,
+                Type::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn compound_operator_test() {
+        let tokens: Vec<_> = Lexer::new("* ** **= && &&= || ||= *=")
+            .map(|t| t.typ)
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                Type::Star,
+                Type::Star2,
+                Type::Star2Eq,
+                Type::Amp2,
+                Type::Amp2Eq,
+                Type::Pipe2,
+                Type::Pipe2Eq,
+                Type::StarEq,
+                Type::EOF
+            ]
+        );
+    }
+}
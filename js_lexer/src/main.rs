@@ -14,10 +14,9 @@ fn main() {
     }
 
     let input = std::fs::read_to_string(filename).expect("Failed to read file");
-    let token_stream = toy_lang_lexer::lexer::token_stream(&input);
-    for token in token_stream
-        .iter()
-        // .filter(|t| t.typ != toy_lang_lexer::token::Type::LineTerminator)
+    let lexer = js_lexer::lexer::Lexer::new(&input);
+    for token in lexer
+        // .filter(|t| t.typ != js_lexer::token::Type::LineTerminator)
     {
         // println!("('{:?}', '{}')", token.typ, &input[token.start..token.end]);
         println!("{:?} \"{}\"", token, &input[token.start..token.end].replace("\n", "\\n"));
@@ -26,15 +25,15 @@ fn main() {
 
 #[test]
 fn test1() {
-    use toy_lang_lexer::token::Type;
-    use toy_lang_lexer::*;
+    use js_lexer::token::Type;
+    use js_lexer::*;
     let input = "var x = 10;";
-    let token_stream = lexer::token_stream(input);
-    let tokens: Vec<_> = token_stream.iter().collect();
-    assert_eq!(tokens.len(), 5);
+    let tokens: Vec<_> = lexer::Lexer::new(input).collect();
+    assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].typ, Type::Var);
     assert_eq!(tokens[1].typ, Type::Identifier);
     assert_eq!(tokens[2].typ, Type::Eq);
     assert_eq!(tokens[3].typ, Type::Decimal);
     assert_eq!(tokens[4].typ, Type::Semicolon);
+    assert_eq!(tokens[5].typ, Type::EOF);
 }
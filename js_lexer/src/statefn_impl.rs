@@ -1,5 +1,6 @@
 //! The implementation of the state functions that the lexer uses to parse the input string.
 
+use crate::error::LexErrorKind;
 use crate::lexer::Lexer;
 use crate::statefn::StateFn;
 use crate::token::Type;
@@ -26,13 +27,9 @@ pub fn lex_start(lexer: &mut Lexer) -> Option<StateFn> {
         ';' => Some(Semicolon),
         '(' => Some(LParen),
         ')' => Some(RParen),
-        '{' => Some(LCurly),
-        '}' => Some(RCurly),
         '[' => Some(LBrack),
         ']' => Some(RBrack),
         '~' => Some(Tilde),
-        '.' => Some(Dot),
-        '?' => Some(Question),
         _ => None,
     } {
         lexer.step();
@@ -57,7 +54,12 @@ pub fn lex_start(lexer: &mut Lexer) -> Option<StateFn> {
         '1'..='9' => sf!(lex_decimal),
         '0' => sf!(lex_zero),
         '"' | '\'' => sf!(lex_string_literal),
+        '`' => sf!(lex_template),
+        '{' => sf!(lex_lcurly),
+        '}' => sf!(lex_rcurly),
         '=' => sf!(lex_eq),
+        '?' => sf!(lex_question),
+        '.' => sf!(lex_dot),
         '!' => sf!(lex_bang),
         '|' => sf!(lex_pipe),
         '<' => sf!(lex_lt),
@@ -83,17 +85,17 @@ pub fn lex_start(lexer: &mut Lexer) -> Option<StateFn> {
             lexer.ignore();
             sf!(lex_start)
         }
-        _ => lex_error(
-            lexer,
-            format!("Unexpected character: {}", c.unwrap()).as_str(),
-        ),
+        c => {
+            lexer.step();
+            lex_error(lexer, LexErrorKind::UnexpectedCharacter(c))
+        }
     }
 }
 
 fn lex_identifier_or_keyword(lexer: &mut Lexer) -> Option<StateFn> {
     lexer.accept_run("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_");
     let s = lexer.current();
-    let typ = Type::match_keyword(&s);
+    let typ = Type::match_keyword(s);
     lexer.emit(typ);
     sf!(lex_start)
 }
@@ -107,7 +109,7 @@ fn lex_zero(lexer: &mut Lexer) -> Option<StateFn> {
                 lexer.emit(Hex);
                 sf!(lex_start)
             } else {
-                lex_error(lexer, "Expected hexadecimal number")
+                lex_error(lexer, LexErrorKind::InvalidHexNumber)
             }
         }
         Some('.') => {
@@ -126,7 +128,7 @@ fn lex_decimal(lexer: &mut Lexer) -> Option<StateFn> {
     if lexer.accept(".") {
         let has_frac = lexer.accept_run("0123456789");
         if !has_int && !has_frac {
-            return lex_error(lexer, "Expected decimal number");
+            return lex_error(lexer, LexErrorKind::InvalidNumber);
         }
     }
     if lexer.accept("eE") {
@@ -142,7 +144,7 @@ fn lex_string_literal(lexer: &mut Lexer) -> Option<StateFn> {
     lexer.step(); // Skip the opening quote
     let check_n_hex = |lexer: &mut Lexer, times: usize| -> bool {
         for _ in 0..times {
-            if !lexer.accept_run("0123456789abcdefABCDEF") {
+            if !lexer.accept("0123456789abcdefABCDEF") {
                 return false;
             }
         }
@@ -166,24 +168,26 @@ fn lex_string_literal(lexer: &mut Lexer) -> Option<StateFn> {
                     }
                     'b' | 'f' | 'n' | 'r' | 't' | 'v' | '"' | '\'' | '\\' => {
                         lexer.step(); // Skip the escape character
+                        continue;
                     }
                     '0' => {
                         lexer.step(); // Skip the zero
                         if let Some(c) = lexer.peek() {
                             if c.is_ascii_digit() {
-                                return lex_error(lexer, "Octal literals are not allowed");
+                                return lex_error(lexer, LexErrorKind::OctalLiteral);
                             }
                         }
+                        continue;
                     }
                     '1'..='9' => {
-                        return lex_error(lexer, "Octal literals are not allowed");
+                        return lex_error(lexer, LexErrorKind::OctalLiteral);
                     }
                     'x' => {
                         lexer.step(); // Skip the x
                         if check_n_hex(lexer, 2) {
                             continue;
                         } else {
-                            return lex_error(lexer, "Expected hexadecimal number");
+                            return lex_error(lexer, LexErrorKind::InvalidHexEscape);
                         }
                     }
                     'u' => {
@@ -191,29 +195,125 @@ fn lex_string_literal(lexer: &mut Lexer) -> Option<StateFn> {
                         if check_n_hex(lexer, 4) {
                             continue;
                         } else {
-                            return lex_error(lexer, "Expected hexadecimal number");
+                            return lex_error(lexer, LexErrorKind::InvalidHexEscape);
                         }
                     }
                     _ => {}
                 }
             } else {
-                return lex_error(lexer, "Unexpected end of input");
+                return lex_error(lexer, LexErrorKind::UnterminatedString);
             }
         } else if c == '\n' || c == '\r' || c == '\u{2028}' || c == '\u{2029}' {
-            return lex_error(lexer, "Unexpected line terminator");
+            return lex_error(lexer, LexErrorKind::UnexpectedLineTerminator);
         }
 
         lexer.step();
     }
-    lex_error(lexer, "Unexpected end of input")
+    lex_error(lexer, LexErrorKind::UnterminatedString)
+}
+
+/// A template literal from its opening backtick, or its continuation after the `}` of a
+/// substitution, up to the next `${` or the closing backtick
+fn lex_template(lexer: &mut Lexer) -> Option<StateFn> {
+    let head = lexer.peek() == Some('`');
+    lexer.step(); // Skip the backtick or `}`
+    while let Some(c) = lexer.peek() {
+        match c {
+            '`' => {
+                lexer.step(); // Skip the closing backtick
+                let typ = if head {
+                    NoSubstitutionTemplate
+                } else {
+                    TemplateTail
+                };
+                lexer.emit(typ);
+                return sf!(lex_start);
+            }
+            '$' => {
+                lexer.step(); // Skip the dollar sign
+                if lexer.accept("{") {
+                    lexer.open_brace(true);
+                    let typ = if head { TemplateHead } else { TemplateMiddle };
+                    lexer.emit(typ);
+                    return sf!(lex_start);
+                }
+            }
+            '\\' => {
+                // Escapes are validated when cooked, invalid ones are allowed in tagged templates
+                lexer.step(); // Skip the backslash
+                lexer.step(); // Skip the escaped character
+            }
+            _ => {
+                lexer.step();
+            }
+        }
+    }
+    lex_error(lexer, LexErrorKind::UnterminatedTemplate)
+}
+
+fn lex_lcurly(lexer: &mut Lexer) -> Option<StateFn> {
+    lexer.step();
+    lexer.open_brace(false);
+    lexer.emit(LCurly);
+    sf!(lex_start)
+}
+
+fn lex_rcurly(lexer: &mut Lexer) -> Option<StateFn> {
+    if lexer.close_brace() {
+        return sf!(lex_template);
+    }
+    lexer.step();
+    lexer.emit(RCurly);
+    sf!(lex_start)
+}
+
+fn lex_dot(lexer: &mut Lexer) -> Option<StateFn> {
+    lexer.step();
+    if lexer.accept(".") {
+        if lexer.accept(".") {
+            lexer.emit(Dot3);
+            return sf!(lex_start);
+        }
+        lexer.back(); // `..` is two dots
+    }
+    lexer.emit(Dot);
+    sf!(lex_start)
+}
+
+fn lex_question(lexer: &mut Lexer) -> Option<StateFn> {
+    lexer.step();
+    if lexer.accept("?") {
+        if lexer.accept("=") {
+            lexer.emit(Question2Eq);
+        } else {
+            lexer.emit(Question2);
+        }
+    } else if lexer.accept(".") {
+        // `a?.5:b` is a conditional expression with a number
+        if lexer.peek().is_some_and(|c| c.is_ascii_digit()) {
+            lexer.back();
+            lexer.emit(Question);
+        } else {
+            lexer.emit(QuestionDot);
+        }
+    } else {
+        lexer.emit(Question);
+    }
+    sf!(lex_start)
 }
 
 fn lex_eq(lexer: &mut Lexer) -> Option<StateFn> {
-    lexer.accept_run("=");
-    match lexer.current().as_str() {
-        "===" => lexer.emit(Eq3),
-        "==" => lexer.emit(Eq2),
-        _ => lexer.emit(Eq),
+    lexer.step();
+    if lexer.accept(">") {
+        lexer.emit(Arrow);
+    } else if lexer.accept("=") {
+        if lexer.accept("=") {
+            lexer.emit(Eq3);
+        } else {
+            lexer.emit(Eq2);
+        }
+    } else {
+        lexer.emit(Eq);
     }
     sf!(lex_start)
 }
@@ -221,7 +321,11 @@ fn lex_eq(lexer: &mut Lexer) -> Option<StateFn> {
 fn lex_pipe(lexer: &mut Lexer) -> Option<StateFn> {
     lexer.step();
     if lexer.accept("|") {
-        lexer.emit(Pipe2);
+        if lexer.accept("=") {
+            lexer.emit(Pipe2Eq);
+        } else {
+            lexer.emit(Pipe2);
+        }
     } else if lexer.accept("=") {
         lexer.emit(PipeEq);
     } else {
@@ -233,9 +337,11 @@ fn lex_pipe(lexer: &mut Lexer) -> Option<StateFn> {
 fn lex_bang(lexer: &mut Lexer) -> Option<StateFn> {
     lexer.step();
     if lexer.accept("=") {
-        lexer.emit(Neq);
-    } else if lexer.accept("!") {
-        lexer.emit(Neq2);
+        if lexer.accept("=") {
+            lexer.emit(Neq2);
+        } else {
+            lexer.emit(Neq);
+        }
     } else {
         lexer.emit(Bang);
     }
@@ -283,7 +389,11 @@ fn lex_gt(lexer: &mut Lexer) -> Option<StateFn> {
 fn lex_amp(lexer: &mut Lexer) -> Option<StateFn> {
     lexer.step();
     if lexer.accept("&") {
-        lexer.emit(Amp2);
+        if lexer.accept("=") {
+            lexer.emit(Amp2Eq);
+        } else {
+            lexer.emit(Amp2);
+        }
     } else if lexer.accept("=") {
         lexer.emit(AmpEq);
     } else {
@@ -296,6 +406,12 @@ fn lex_star(lexer: &mut Lexer) -> Option<StateFn> {
     lexer.step();
     if lexer.accept("=") {
         lexer.emit(StarEq);
+    } else if lexer.accept("*") {
+        if lexer.accept("=") {
+            lexer.emit(Star2Eq);
+        } else {
+            lexer.emit(Star2);
+        }
     } else {
         lexer.emit(Star);
     }
@@ -361,6 +477,39 @@ fn lex_percent(lexer: &mut Lexer) -> Option<StateFn> {
     sf!(lex_start)
 }
 
+/// A regular expression literal, only lexed at the parser's request: a `/` that starts an
+/// expression cannot be a division
+pub(crate) fn lex_regex(lexer: &mut Lexer) -> Option<StateFn> {
+    lexer.step(); // Skip the opening slash
+    let mut in_class = false;
+    while let Some(c) = lexer.peek() {
+        match c {
+            '\n' | '\r' | '\u{2028}' | '\u{2029}' => break,
+            '\\' => {
+                lexer.step(); // Skip the backslash
+                if matches!(lexer.peek(), Some('\n' | '\r' | '\u{2028}' | '\u{2029}')) {
+                    break;
+                }
+            }
+            // a `/` inside a class does not end the pattern: `/[/]/`
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => {
+                lexer.step(); // Skip the closing slash
+                let is_flag = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '$');
+                while lexer.peek().is_some_and(is_flag) {
+                    lexer.step(); // The flags are validated by the parser
+                }
+                lexer.emit(RegExp);
+                return sf!(lex_start);
+            }
+            _ => {}
+        }
+        lexer.step();
+    }
+    lex_error(lexer, LexErrorKind::UnterminatedRegExp)
+}
+
 fn lex_single_line_comment(lexer: &mut Lexer) -> Option<StateFn> {
     lexer.accept("//");
     while let Some(c) = lexer.peek() {
@@ -370,7 +519,8 @@ fn lex_single_line_comment(lexer: &mut Lexer) -> Option<StateFn> {
         }
         lexer.step();
     }
-    lex_error(lexer, "Unexpected end of input")
+    lexer.ignore();
+    sf!(lex_start)
 }
 
 /// simplified
@@ -383,15 +533,18 @@ fn lex_multi_line_comment(lexer: &mut Lexer) -> Option<StateFn> {
                 lexer.ignore();
                 return sf!(lex_start);
             }
+            continue;
+        }
+        if matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+            lexer.mark_new_line();
         }
         lexer.step();
     }
-    lex_error(lexer, "Unexpected end of input")
+    lex_error(lexer, LexErrorKind::UnterminatedComment)
 }
 
-// TODO: error handling
-fn lex_error(lexer: &mut Lexer, msg: &str) -> Option<StateFn> {
-    lexer.send(LexerError);
-    lexer.error(msg);
+/// Report the error and stop lexing
+fn lex_error(lexer: &mut Lexer, kind: LexErrorKind) -> Option<StateFn> {
+    lexer.fail(kind);
     None
 }
@@ -3,6 +3,8 @@ pub struct Token {
     pub typ: Type,
     pub start: usize,
     pub end: usize,
+    /// A line terminator (possibly inside a comment) separates this token from the previous one
+    pub is_on_new_line: bool,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -67,13 +69,16 @@ pub enum Type {
     // punctuator
     Amp,           // &
     Amp2,          // &&
+    Amp2Eq,        // &&=
     AmpEq,         // &=
+    Arrow,         // =>
     Bang,          // !
     Caret,         // ^
     CaretEq,       // ^=
     Colon,         // :
     Comma,         // ,
     Dot,           // .
+    Dot3,          // ...
     Eq,            // =
     Eq2,           // ==
     Eq3,           // ===
@@ -92,11 +97,15 @@ pub enum Type {
     PercentEq,     // %=
     Pipe,          // |
     Pipe2,         // ||
+    Pipe2Eq,       // ||=
     PipeEq,        // |=
     Plus,          // +
     Plus2,         // ++
     PlusEq,        // +=
     Question,      // ?
+    Question2,     // ??
+    Question2Eq,   // ??=
+    QuestionDot,   // ?.
     RAngle,        // >
     RBrack,        // ]
     RCurly,        // }
@@ -110,6 +119,8 @@ pub enum Type {
     ShiftRight3Eq, // >>>=
     Star,          // *
     StarEq,        // *=
+    Star2,         // **
+    Star2Eq,       // **=
     Tilde,         // ~
     // div punctuator
     Slash,   // /
@@ -127,6 +138,13 @@ pub enum Type {
     // String Literals
     /// String Type
     Str,
+    // Template Literals
+    NoSubstitutionTemplate, // `a`
+    TemplateHead,           // `a${
+    TemplateMiddle,         // }a${
+    TemplateTail,           // }a`
+    // Regular Expression Literals, only produced by `Lexer::relex_regex`
+    RegExp, // /a/g
 
     // Other
     Identifier,
@@ -202,13 +220,16 @@ impl Type {
             Yield => "yield",
             Amp => "&",
             Amp2 => "&&",
+            Amp2Eq => "&&=",
             AmpEq => "&=",
+            Arrow => "=>",
             Bang => "!",
             Caret => "^",
             CaretEq => "^=",
             Colon => ":",
             Comma => ",",
             Dot => ".",
+            Dot3 => "...",
             Eq => "=",
             Eq2 => "==",
             Eq3 => "===",
@@ -227,11 +248,15 @@ impl Type {
             PercentEq => "%=",
             Pipe => "|",
             Pipe2 => "||",
+            Pipe2Eq => "||=",
             PipeEq => "|=",
             Plus => "+",
             Plus2 => "++",
             PlusEq => "+=",
             Question => "?",
+            Question2 => "??",
+            Question2Eq => "??=",
+            QuestionDot => "?.",
             RAngle => ">",
             RBrack => "]",
             RCurly => "}",
@@ -247,6 +272,8 @@ impl Type {
             SlashEq => "/=",
             Star => "*",
             StarEq => "*=",
+            Star2 => "**",
+            Star2Eq => "**=",
             Tilde => "~",
             Null => "null",
             True => "true",
@@ -254,6 +281,11 @@ impl Type {
             Decimal => "decimal",
             Hex => "hex",
             Str => "string",
+            NoSubstitutionTemplate => "template",
+            TemplateHead => "template head",
+            TemplateMiddle => "template middle",
+            TemplateTail => "template tail",
+            RegExp => "regexp",
             LineTerminator => "lineterminator",
             _ => unreachable!("unreachable token type: {:?}", self),
         }
@@ -335,6 +367,7 @@ impl std::default::Default for Token {
             typ: Type::EOF,
             start: 0,
             end: 0,
+            is_on_new_line: false,
         }
     }
 }
@@ -19,10 +19,21 @@ impl Span {
 #[derive(Debug, Clone, Serialize)]
 pub struct Program<'a> {
     pub span: Span,
+    pub source_type: SourceType,
     pub source_text: &'a str,
     pub body: Vec<Statement<'a>>,
 }
 
+/// The goal symbol the source was parsed with
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SourceType {
+    #[default]
+    Script,
+    /// Strict mode code that may contain `import` and `export` declarations. Of the strict mode
+    /// early errors, only duplicate parameters and `delete` of an identifier are reported.
+    Module,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub enum Statement<'a> {
     BlockStatement(Box<BlockStatement<'a>>),
@@ -31,14 +42,31 @@ pub enum Statement<'a> {
     EmptyStatement(Box<EmptyStatement>),
     ReturnStatement(Box<ReturnStatement<'a>>),
     ForStatement(Box<ForStatement<'a>>),
+    ForInStatement(Box<ForInStatement<'a>>),
+    ForOfStatement(Box<ForOfStatement<'a>>),
     WhileStatement(Box<WhileStatement<'a>>),
-    BreakStatement(Box<BreakStatement>),
-    ContinueStatement(Box<ContinueStatement>),
+    BreakStatement(Box<BreakStatement<'a>>),
+    ContinueStatement(Box<ContinueStatement<'a>>),
+    LabeledStatement(Box<LabeledStatement<'a>>),
     DoWhileStatement(Box<DoWhileStatement<'a>>),
     SwitchStatement(Box<SwitchStatement<'a>>),
     WithStatement(Box<WithStatement<'a>>),
     VariableDeclarationStatement(Box<VariableDeclaration<'a>>),
     FunctionDeclaration(Box<Function<'a>>),
+    ClassDeclaration(Box<Class<'a>>),
+    ThrowStatement(Box<ThrowStatement<'a>>),
+    TryStatement(Box<TryStatement<'a>>),
+    ImportDeclaration(Box<ImportDeclaration<'a>>),
+    ExportNamedDeclaration(Box<ExportNamedDeclaration<'a>>),
+    ExportDefaultDeclaration(Box<ExportDefaultDeclaration<'a>>),
+    ExportAllDeclaration(Box<ExportAllDeclaration<'a>>),
+    /// Placeholder for a statement that failed to parse in recovery mode
+    Error(Box<ErrorStatement>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorStatement {
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -76,20 +104,51 @@ pub enum ForStatementInit<'a> {
     Expression(Expression<'a>),
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ForInStatement<'a> {
+    pub span: Span,
+    pub left: ForStatementLeft<'a>,
+    pub right: Expression<'a>,
+    pub body: Statement<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForOfStatement<'a> {
+    pub span: Span,
+    /// `for await (...)`
+    pub r#await: bool,
+    pub left: ForStatementLeft<'a>,
+    pub right: Expression<'a>,
+    pub body: Statement<'a>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub enum ForStatementLeft<'a> {
+    /// A single declarator without an initializer
     VariableDeclaration(Box<VariableDeclaration<'a>>),
-    Expression(Expression<'a>),
+    AssignmentTarget(AssignmentTarget<'a>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContinueStatement<'a> {
+    pub span: Span,
+    /// The label of the loop to continue, instead of the innermost one
+    pub label: Option<Identifier<'a>>,
 }
 
 #[derive(Debug, Clone, Serialize)]
-pub struct ContinueStatement {
+pub struct BreakStatement<'a> {
     pub span: Span,
+    /// The label of the statement to leave, instead of the innermost loop or `switch`
+    pub label: Option<Identifier<'a>>,
 }
 
+/// `label: body`
 #[derive(Debug, Clone, Serialize)]
-pub struct BreakStatement {
+pub struct LabeledStatement<'a> {
     pub span: Span,
+    pub label: Identifier<'a>,
+    pub body: Statement<'a>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -113,6 +172,111 @@ pub struct SwitchCase<'a> {
     pub consequent: Vec<Statement<'a>>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ThrowStatement<'a> {
+    pub span: Span,
+    pub argument: Expression<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TryStatement<'a> {
+    pub span: Span,
+    pub block: Box<BlockStatement<'a>>,
+    pub handler: Option<Box<CatchClause<'a>>>,
+    pub finalizer: Option<Box<BlockStatement<'a>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CatchClause<'a> {
+    pub span: Span,
+    /// `None` for `catch { ... }` without a binding
+    pub param: Option<BindingPattern<'a>>,
+    pub body: Box<BlockStatement<'a>>,
+}
+
+/// `import a, { b as c } from 'x';`, `import * as ns from 'x';` or `import 'x';`
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportDeclaration<'a> {
+    pub span: Span,
+    pub specifiers: Vec<ImportDeclarationSpecifier<'a>>,
+    pub source: StringLiteral<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum ImportDeclarationSpecifier<'a> {
+    /// `{ imported as local }`
+    ImportSpecifier(Box<ImportSpecifier<'a>>),
+    /// `local`
+    ImportDefaultSpecifier(Box<ImportDefaultSpecifier<'a>>),
+    /// `* as local`
+    ImportNamespaceSpecifier(Box<ImportNamespaceSpecifier<'a>>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportSpecifier<'a> {
+    pub span: Span,
+    pub imported: ModuleExportName<'a>,
+    pub local: Identifier<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportDefaultSpecifier<'a> {
+    pub span: Span,
+    pub local: Identifier<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportNamespaceSpecifier<'a> {
+    pub span: Span,
+    pub local: Identifier<'a>,
+}
+
+/// `export var a;`, `export { a as b };` or `export { a } from 'x';`
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportNamedDeclaration<'a> {
+    pub span: Span,
+    /// A variable, function or class declaration, exclusive with `specifiers`
+    pub declaration: Option<Statement<'a>>,
+    pub specifiers: Vec<ExportSpecifier<'a>>,
+    pub source: Option<StringLiteral<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSpecifier<'a> {
+    pub span: Span,
+    pub local: ModuleExportName<'a>,
+    pub exported: ModuleExportName<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportDefaultDeclaration<'a> {
+    pub span: Span,
+    pub declaration: ExportDefaultDeclarationKind<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum ExportDefaultDeclarationKind<'a> {
+    /// The name is optional
+    FunctionDeclaration(Box<Function<'a>>),
+    /// The name is optional
+    ClassDeclaration(Box<Class<'a>>),
+    Expression(Expression<'a>),
+}
+
+/// `export * from 'x';` or `export * as ns from 'x';`
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportAllDeclaration<'a> {
+    pub span: Span,
+    pub exported: Option<ModuleExportName<'a>>,
+    pub source: StringLiteral<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum ModuleExportName<'a> {
+    IdentifierName(IdentifierName<'a>),
+    StringLiteral(StringLiteral<'a>),
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct EmptyStatement {
     pub span: Span,
@@ -142,7 +306,13 @@ pub struct IfStatement<'a> {
 pub struct VariableDeclaration<'a> {
     pub span: Span,
     pub kind: VariableDeclarationKind,
-    pub id: Identifier<'a>,
+    pub declarations: Vec<VariableDeclarator<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VariableDeclarator<'a> {
+    pub span: Span,
+    pub id: BindingPattern<'a>,
     pub init: Option<Expression<'a>>,
 }
 
@@ -159,12 +329,80 @@ pub struct Function<'a> {
     pub id: Option<Identifier<'a>>,
     pub params: Box<FormalParameters<'a>>,
     pub body: Option<Box<FunctionBody<'a>>>,
+    /// `async function` or `async m() {}`
+    pub is_async: bool,
+    /// `function*` or `*m() {}`
+    pub is_generator: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct FormalParameters<'a> {
     pub span: Span,
-    pub params: Vec<Identifier<'a>>,
+    pub params: Vec<BindingPattern<'a>>,
+    /// `...rest`, after the other parameters
+    pub rest: Option<Box<RestElement<'a>>>,
+}
+
+/// The target of a declaration, parameter or catch clause
+#[derive(Debug, Clone, Serialize)]
+pub enum BindingPattern<'a> {
+    BindingIdentifier(Box<Identifier<'a>>),
+    ObjectPattern(Box<ObjectPattern<'a>>),
+    ArrayPattern(Box<ArrayPattern<'a>>),
+    /// An element with a default value, `a = 1` in `[a = 1]`
+    AssignmentPattern(Box<AssignmentPattern<'a>>),
+}
+
+/// `{ a, b: c = 1, ...rest }`
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectPattern<'a> {
+    pub span: Span,
+    pub properties: Vec<BindingProperty<'a>>,
+    /// Always a [`BindingPattern::BindingIdentifier`]
+    pub rest: Option<Box<RestElement<'a>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BindingProperty<'a> {
+    pub span: Span,
+    pub key: PropertyKey<'a>,
+    pub value: BindingPattern<'a>,
+    /// `{ a }` or `{ a = 1 }`, where the key is also the bound name
+    pub shorthand: bool,
+}
+
+/// `[a, , b = 1, ...rest]`
+#[derive(Debug, Clone, Serialize)]
+pub struct ArrayPattern<'a> {
+    pub span: Span,
+    /// `None` for holes
+    pub elements: Vec<Option<BindingPattern<'a>>>,
+    pub rest: Option<Box<RestElement<'a>>>,
+}
+
+/// `...rest`
+#[derive(Debug, Clone, Serialize)]
+pub struct RestElement<'a> {
+    pub span: Span,
+    pub argument: BindingPattern<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssignmentPattern<'a> {
+    pub span: Span,
+    pub left: BindingPattern<'a>,
+    pub right: Expression<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArrowFunctionExpression<'a> {
+    pub span: Span,
+    /// The body is a concise expression, held as the single [`ExpressionStatement`] of `body`
+    pub expression: bool,
+    /// `async x => x` or `async (x) => x`
+    pub is_async: bool,
+    pub params: Box<FormalParameters<'a>>,
+    pub body: Box<FunctionBody<'a>>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -173,35 +411,116 @@ pub struct FunctionBody<'a> {
     pub statements: Vec<Statement<'a>>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct Class<'a> {
+    pub span: Span,
+    pub id: Option<Identifier<'a>>,
+    pub super_class: Option<Expression<'a>>,
+    pub body: Box<ClassBody<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassBody<'a> {
+    pub span: Span,
+    pub body: Vec<ClassElement<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum ClassElement<'a> {
+    MethodDefinition(Box<MethodDefinition<'a>>),
+    PropertyDefinition(Box<PropertyDefinition<'a>>),
+    StaticBlock(Box<StaticBlock<'a>>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MethodDefinition<'a> {
+    pub span: Span,
+    pub r#static: bool,
+    pub kind: MethodDefinitionKind,
+    pub key: PropertyKey<'a>,
+    /// The parameters and body, `id` is always `None`
+    pub value: Box<Function<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MethodDefinitionKind {
+    Constructor,
+    Method,
+    Get,
+    Set,
+}
+
+/// A class field, `static x = 1;`
+#[derive(Debug, Clone, Serialize)]
+pub struct PropertyDefinition<'a> {
+    pub span: Span,
+    pub r#static: bool,
+    pub key: PropertyKey<'a>,
+    pub value: Option<Expression<'a>>,
+}
+
+/// `static { ... }`
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticBlock<'a> {
+    pub span: Span,
+    pub body: Vec<Statement<'a>>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub enum Expression<'a> {
     BooleanLiteral(Box<BooleanLiteral>),
     NullLiteral(Box<NullLiteral>),
     NumericLiteral(Box<NumericLiteral<'a>>),
     StringLiteral(Box<StringLiteral<'a>>),
+    TemplateLiteral(Box<TemplateLiteral<'a>>),
+    RegExpLiteral(Box<RegExpLiteral<'a>>),
     Identifier(Box<Identifier<'a>>),
     SequenceExpression(Box<SequenceExpression<'a>>),
     BinaryExpression(Box<BinaryExpression<'a>>),
     UnaryExpression(Box<UnaryExpression<'a>>),
+    UpdateExpression(Box<UpdateExpression<'a>>),
     LogicalExpression(Box<LogicalExpression<'a>>),
+    ConditionalExpression(Box<ConditionalExpression<'a>>),
     AssignmentExpression(Box<AssignmentExpression<'a>>),
     ArrayExpression(Box<ArrayExpression<'a>>),
     ObjectExpression(Box<ObjectExpression<'a>>),
     StaticMemberExpression(Box<StaticMemberExpression<'a>>),
     ComputedMemberExpression(Box<ComputedMemberExpression<'a>>),
     CallExpression(Box<CallExpression<'a>>),
+    NewExpression(Box<NewExpression<'a>>),
+    /// ``tag`a${b}` ``
+    TaggedTemplateExpression(Box<TaggedTemplateExpression<'a>>),
+    /// `a?.b.c`, the extent of an optional chain
+    ChainExpression(Box<ChainExpression<'a>>),
+    ThisExpression(Box<ThisExpression>),
+    MetaProperty(Box<MetaProperty<'a>>),
+    FunctionExpression(Box<Function<'a>>),
+    ClassExpression(Box<Class<'a>>),
+    /// `import(source)`
+    ImportExpression(Box<ImportExpression<'a>>),
+    /// `super` as the callee of `super(...)` or the object of `super.x`
+    Super(Box<Super>),
+    ArrowFunctionExpression(Box<ArrowFunctionExpression<'a>>),
     ParenthesizedExpression(Box<ParenthesizedExpression<'a>>),
+    /// `await a`, only in async functions and modules
+    AwaitExpression(Box<AwaitExpression<'a>>),
+    /// `yield a` or `yield* a`, only in generators
+    YieldExpression(Box<YieldExpression<'a>>),
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ArrayExpression<'a> {
     pub span: Span,
     pub elements: Vec<ArrayExpressionElement<'a>>,
+    /// The comma after the last element, which a rest element cannot have when the array is
+    /// reinterpreted as a pattern
+    pub trailing_comma: Option<Span>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub enum ArrayExpressionElement<'a> {
     Elision(Elision),
+    SpreadElement(Box<SpreadElement<'a>>),
     Expression(Expression<'a>),
 }
 
@@ -214,15 +533,36 @@ pub struct Elision {
 pub struct ObjectExpression<'a> {
     pub span: Span,
     /// Properties declared in the object
-    pub properties: Vec<ObjectProperty<'a>>,
+    pub properties: Vec<ObjectPropertyKind<'a>>,
     pub trailing_comma: Option<Span>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub enum ObjectPropertyKind<'a> {
+    ObjectProperty(Box<ObjectProperty<'a>>),
+    /// `...a`
+    SpreadProperty(Box<SpreadElement<'a>>),
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ObjectProperty<'a> {
     pub span: Span,
+    pub kind: PropertyKind,
     pub key: PropertyKey<'a>,
+    /// A [`Expression::FunctionExpression`] for methods and accessors
     pub value: Expression<'a>,
+    /// `{ a }`, where the key is also the value
+    pub shorthand: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PropertyKind {
+    /// `a: 1` or `a`
+    Init,
+    /// `a() {}`, including async and generator methods
+    Method,
+    Get,
+    Set,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -230,6 +570,8 @@ pub enum PropertyKey<'a> {
     IdentifierName(IdentifierName<'a>),
     StringLiteral(StringLiteral<'a>),
     NumericLiteral(NumericLiteral<'a>),
+    /// `[key]`
+    Computed(Expression<'a>),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -253,11 +595,36 @@ pub struct UnaryExpression<'a> {
     pub argument: Expression<'a>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct AwaitExpression<'a> {
+    pub span: Span,
+    pub argument: Expression<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct YieldExpression<'a> {
+    pub span: Span,
+    /// `yield*`, which yields each value of the iterable argument
+    pub delegate: bool,
+    pub argument: Option<Expression<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateExpression<'a> {
+    pub span: Span,
+    pub operator: UpdateOperator,
+    /// `++a` rather than `a++`
+    pub prefix: bool,
+    pub argument: AssignmentTarget<'a>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct StaticMemberExpression<'a> {
     pub span: Span,
     pub object: Expression<'a>,
     pub property: IdentifierName<'a>,
+    /// `a?.b`
+    pub optional: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -265,13 +632,62 @@ pub struct ComputedMemberExpression<'a> {
     pub span: Span,
     pub object: Expression<'a>,
     pub expression: Expression<'a>,
+    /// `a?.[b]`
+    pub optional: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct CallExpression<'a> {
     pub span: Span,
     pub callee: Expression<'a>,
-    pub arguments: Vec<Expression<'a>>,
+    pub arguments: Vec<Argument<'a>>,
+    /// `a?.()`
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewExpression<'a> {
+    pub span: Span,
+    pub callee: Expression<'a>,
+    /// Empty for both `new C()` and `new C`
+    pub arguments: Vec<Argument<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum Argument<'a> {
+    SpreadElement(Box<SpreadElement<'a>>),
+    Expression(Expression<'a>),
+}
+
+/// `...iterable` in arguments and array literals
+#[derive(Debug, Clone, Serialize)]
+pub struct SpreadElement<'a> {
+    pub span: Span,
+    pub argument: Expression<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ThisExpression {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportExpression<'a> {
+    pub span: Span,
+    pub source: Expression<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Super {
+    pub span: Span,
+}
+
+/// `new.target` or `import.meta`
+#[derive(Debug, Clone, Serialize)]
+pub struct MetaProperty<'a> {
+    pub span: Span,
+    pub meta: IdentifierName<'a>,
+    pub property: IdentifierName<'a>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -287,6 +703,54 @@ pub enum AssignmentTarget<'a> {
     Identifier(Box<Identifier<'a>>),
     StaticMemberExpression(Box<StaticMemberExpression<'a>>),
     ComputedMemberExpression(Box<ComputedMemberExpression<'a>>),
+    /// An array literal on the left of `=`, `[a, b] = [b, a]`
+    ArrayAssignmentTarget(Box<ArrayAssignmentTarget<'a>>),
+    /// An object literal on the left of `=`, `({ a: x.y } = o)`
+    ObjectAssignmentTarget(Box<ObjectAssignmentTarget<'a>>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArrayAssignmentTarget<'a> {
+    pub span: Span,
+    /// `None` for holes
+    pub elements: Vec<Option<AssignmentTargetMaybeDefault<'a>>>,
+    pub rest: Option<Box<AssignmentTargetRest<'a>>>,
+}
+
+/// `...rest` in `[a, ...rest] = b`
+#[derive(Debug, Clone, Serialize)]
+pub struct AssignmentTargetRest<'a> {
+    pub span: Span,
+    pub target: AssignmentTarget<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectAssignmentTarget<'a> {
+    pub span: Span,
+    pub properties: Vec<AssignmentTargetProperty<'a>>,
+    /// `...rest`, after the other properties
+    pub rest: Option<Box<AssignmentTargetRest<'a>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssignmentTargetProperty<'a> {
+    pub span: Span,
+    pub key: PropertyKey<'a>,
+    pub value: AssignmentTargetMaybeDefault<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum AssignmentTargetMaybeDefault<'a> {
+    AssignmentTarget(AssignmentTarget<'a>),
+    /// `a = 1` in `[a = 1] = b`
+    AssignmentTargetWithDefault(Box<AssignmentTargetWithDefault<'a>>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssignmentTargetWithDefault<'a> {
+    pub span: Span,
+    pub binding: AssignmentTarget<'a>,
+    pub init: Expression<'a>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -297,6 +761,21 @@ pub struct LogicalExpression<'a> {
     pub right: Expression<'a>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ConditionalExpression<'a> {
+    pub span: Span,
+    pub test: Expression<'a>,
+    pub consequent: Expression<'a>,
+    pub alternate: Expression<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainExpression<'a> {
+    pub span: Span,
+    /// A member or call expression with at least one `optional` link
+    pub expression: Expression<'a>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ParenthesizedExpression<'a> {
     pub span: Span,
@@ -327,6 +806,47 @@ pub struct StringLiteral<'a> {
     pub value: &'a str,
 }
 
+/// `/pattern/flags`
+#[derive(Debug, Clone, Serialize)]
+pub struct RegExpLiteral<'a> {
+    pub span: Span,
+    /// The source text between the slashes
+    pub pattern: &'a str,
+    pub flags: &'a str,
+}
+
+/// `` `a${b}c` ``, with one more quasi than expressions
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateLiteral<'a> {
+    pub span: Span,
+    pub quasis: Vec<TemplateElement<'a>>,
+    pub expressions: Vec<Expression<'a>>,
+}
+
+/// The text of a template literal between its substitutions
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateElement<'a> {
+    pub span: Span,
+    /// The last quasi, before the closing backtick
+    pub tail: bool,
+    pub value: TemplateElementValue<'a>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateElementValue<'a> {
+    /// The source text, with escapes left as written
+    pub raw: &'a str,
+    /// The text with escapes interpreted, `None` for an invalid escape in a tagged template
+    pub cooked: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaggedTemplateExpression<'a> {
+    pub span: Span,
+    pub tag: Expression<'a>,
+    pub quasi: TemplateLiteral<'a>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Identifier<'a> {
     pub span: Span,
@@ -0,0 +1,179 @@
+use super::*;
+use crate::ast::*;
+use syntax::lex::TokenTypeUtil;
+
+impl<'a> Parser<'a> {
+    #[allow(unused_variables)]
+    pub(crate) fn parse_class_declaration(
+        &mut self,
+        stmt_ctx: StatementContext,
+    ) -> Result<Statement<'a>> {
+        let span = self.start_span();
+        self.expect(Type::Class)?;
+        // only `export default class {}` may omit the name
+        if !self.cur_kind().is_identifier() {
+            return Err(self.unexpected());
+        }
+        let class = self.parse_class(span)?;
+        Ok(Statement::ClassDeclaration(class))
+    }
+
+    pub(crate) fn parse_class_expression(&mut self) -> Result<Expression<'a>> {
+        let span = self.start_span();
+        self.expect(Type::Class)?;
+        self.parse_class(span).map(Expression::ClassExpression)
+    }
+
+    /// Section 15.7 Class Definitions, after the `class` keyword
+    pub(crate) fn parse_class(&mut self, span: Span) -> Result<Box<Class<'a>>> {
+        let id = self.parse_function_id()?;
+        let super_class = if self.eat(Type::Extends) {
+            Some(self.parse_lhs_expression_or_higher()?)
+        } else {
+            None
+        };
+        let body = self.parse_class_body()?;
+        Ok(Box::new(Class {
+            span: self.end_span(span),
+            id,
+            super_class,
+            body,
+        }))
+    }
+
+    fn parse_class_body(&mut self) -> Result<Box<ClassBody<'a>>> {
+        let span = self.start_span();
+        self.expect(Type::LCurly)?;
+        let mut body = vec![];
+        let mut has_constructor = false;
+        while !self.at(Type::RCurly) {
+            if self.eat(Type::Semicolon) {
+                continue;
+            }
+            // class bodies are strict mode code
+            let element = self.context(
+                Context::In | Context::Strict,
+                Context::empty(),
+                Self::parse_class_element,
+            )?;
+            if let ClassElement::MethodDefinition(method) = &element {
+                if method.kind == MethodDefinitionKind::Constructor {
+                    if has_constructor {
+                        return Err(ParseError::DuplicateConstructor { span: method.span });
+                    }
+                    has_constructor = true;
+                }
+            }
+            body.push(element);
+        }
+        self.expect(Type::RCurly)?;
+        Ok(Box::new(ClassBody {
+            span: self.end_span(span),
+            body,
+        }))
+    }
+
+    fn parse_class_element(&mut self) -> Result<ClassElement<'a>> {
+        let span = self.start_span();
+
+        // `static`, `get`, `set` and `async` are only modifiers when another name follows
+        let mut is_generator = self.eat(Type::Star);
+        let mut is_async = false;
+        let mut modifier = self.cur_kind();
+        let mut key = self.parse_property_key()?;
+        let r#static =
+            !is_generator && modifier == Type::Static && !self.at_class_element_name_end(modifier);
+        if r#static {
+            if self.eat(Type::LCurly) {
+                // a static block is a function body of its own, outside any async function
+                let body =
+                    self.context(Context::Function, Context::Await | Context::Yield, |p| {
+                        p.parse_statements(false)
+                    })?;
+                self.expect(Type::RCurly)?;
+                return Ok(ClassElement::StaticBlock(Box::new(StaticBlock {
+                    span: self.end_span(span),
+                    body,
+                })));
+            }
+            is_generator = self.eat(Type::Star);
+            modifier = self.cur_kind();
+            key = self.parse_property_key()?;
+        }
+
+        let mut kind = MethodDefinitionKind::Method;
+        if !is_generator && !self.at_class_element_name_end(modifier) {
+            match modifier {
+                Type::Get => kind = MethodDefinitionKind::Get,
+                Type::Set => kind = MethodDefinitionKind::Set,
+                Type::Async => {
+                    is_async = true;
+                    is_generator = self.eat(Type::Star);
+                }
+                _ => return Err(self.unexpected()),
+            }
+            key = self.parse_property_key()?;
+        }
+        // only a plain name can also be a field
+        if is_async || is_generator || kind != MethodDefinitionKind::Method {
+            self.expect_peek_only(Type::LParen)?;
+        }
+
+        if !self.at(Type::LParen) {
+            let value = if self.eat(Type::Eq) {
+                // an initializer is evaluated like the body of a method
+                Some(self.context(
+                    Context::Function,
+                    Context::empty(),
+                    Self::parse_assignment_expression_or_higher,
+                )?)
+            } else {
+                None
+            };
+            self.auto_semicoclon_insertion()?;
+            return Ok(ClassElement::PropertyDefinition(Box::new(
+                PropertyDefinition {
+                    span: self.end_span(span),
+                    r#static,
+                    key,
+                    value,
+                },
+            )));
+        }
+
+        let plain = kind == MethodDefinitionKind::Method && !is_async && !is_generator;
+        if plain && !r#static && is_constructor(&key) {
+            kind = MethodDefinitionKind::Constructor;
+        }
+        let value = self.parse_method_function(is_async, is_generator)?;
+        Ok(ClassElement::MethodDefinition(Box::new(MethodDefinition {
+            span: self.end_span(span),
+            r#static,
+            kind,
+            key,
+            value,
+        })))
+    }
+
+    /// Whether the name just parsed, which started with the token `modifier`, is the whole name
+    /// of a method or field
+    fn at_class_element_name_end(&self, modifier: Type) -> bool {
+        // `get`, `set` and `static` may be followed by a name on the next line, while
+        // [no LineTerminator here] after `async`, and a field without `;` ends at a line break
+        matches!(
+            self.cur_kind(),
+            Type::LParen | Type::Eq | Type::Semicolon | Type::RCurly
+        ) || (self.cur_token().is_on_new_line
+            && !matches!(modifier, Type::Get | Type::Set | Type::Static))
+    }
+}
+
+fn is_constructor(key: &PropertyKey) -> bool {
+    match key {
+        PropertyKey::IdentifierName(ident) => ident.name == "constructor",
+        PropertyKey::StringLiteral(lit) => {
+            lit.value.get(1..lit.value.len() - 1) == Some("constructor")
+        }
+        PropertyKey::NumericLiteral(_) | PropertyKey::Computed(_) => false,
+    }
+}
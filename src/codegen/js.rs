@@ -0,0 +1,1518 @@
+//! JavaScript code generator
+//!
+//! Prints a [`Program`] back to JavaScript source. Redundant parentheses from the source are
+//! dropped; parentheses are only emitted where a child expression has a lower [`Precedence`]
+//! than its position requires.
+
+use crate::ast::*;
+use crate::syntax::operator::*;
+use crate::syntax::precedence::Precedence;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    Tab,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quote {
+    Single,
+    Double,
+    /// Keep the quotes used in the source
+    Preserve,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsCodegenOptions {
+    pub indent: Indent,
+    pub quote: Quote,
+    /// Terminate statements with `;` instead of relying on automatic semicolon insertion
+    pub semicolons: bool,
+}
+
+impl Default for JsCodegenOptions {
+    fn default() -> Self {
+        Self {
+            indent: Indent::Spaces(2),
+            quote: Quote::Preserve,
+            semicolons: true,
+        }
+    }
+}
+
+pub struct JsCodegen<'a> {
+    options: JsCodegenOptions,
+    /// Source of the program being printed, copied verbatim for statements that failed to parse
+    source_text: &'a str,
+    code: String,
+    indent: usize,
+}
+
+impl<'a> JsCodegen<'a> {
+    pub fn new(options: JsCodegenOptions) -> Self {
+        Self {
+            options,
+            source_text: "",
+            code: String::new(),
+            indent: 0,
+        }
+    }
+
+    pub fn build(mut self, program: &Program<'a>) -> String {
+        self.source_text = program.source_text;
+        for stmt in &program.body {
+            self.print_statement(stmt);
+            self.code.push('\n');
+        }
+        self.code
+    }
+}
+
+// helpers:
+impl JsCodegen<'_> {
+    fn print_indent(&mut self) {
+        for _ in 0..self.indent {
+            match self.options.indent {
+                Indent::Spaces(width) => self.code.extend(std::iter::repeat_n(' ', width)),
+                Indent::Tab => self.code.push('\t'),
+            }
+        }
+    }
+
+    fn print_semicolon(&mut self) {
+        if self.options.semicolons {
+            self.code.push(';');
+        }
+    }
+
+    fn print_list<T>(&mut self, items: &[T], mut print: impl FnMut(&mut Self, &T)) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.code.push_str(", ");
+            }
+            print(self, item);
+        }
+    }
+
+    fn print_string(&mut self, raw: &str) {
+        let target = match self.options.quote {
+            Quote::Single => '\'',
+            Quote::Double => '"',
+            Quote::Preserve => {
+                self.code.push_str(raw);
+                return;
+            }
+        };
+        let mut chars = raw.chars();
+        let Some(source) = chars.next() else {
+            return;
+        };
+        chars.next_back();
+        self.code.push(target);
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    // `\'` in a double quoted string does not need the escape anymore
+                    Some(next) if next == source && next != target => self.code.push(next),
+                    Some(next) => {
+                        self.code.push('\\');
+                        self.code.push(next);
+                    }
+                    None => self.code.push('\\'),
+                },
+                c if c == target => {
+                    self.code.push('\\');
+                    self.code.push(c);
+                }
+                c => self.code.push(c),
+            }
+        }
+        self.code.push(target);
+    }
+}
+
+// statements:
+impl JsCodegen<'_> {
+    fn print_statement(&mut self, stmt: &Statement) {
+        self.print_indent();
+        self.print_statement_unindented(stmt);
+    }
+
+    /// A statement that continues the current line
+    fn print_statement_unindented(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::BlockStatement(block) => self.print_block(&block.body),
+            Statement::IfStatement(stmt) => self.print_if_statement(stmt),
+            Statement::ExpressionStatement(stmt) => {
+                self.print_expression_statement(&stmt.expression);
+                self.print_semicolon();
+            }
+            Statement::EmptyStatement(_) => self.code.push(';'),
+            Statement::ReturnStatement(stmt) => {
+                self.code.push_str("return");
+                if let Some(argument) = &stmt.argument {
+                    self.code.push(' ');
+                    self.print_expression(argument, Precedence::Lowest);
+                }
+                self.print_semicolon();
+            }
+            Statement::ForStatement(stmt) => {
+                self.code.push_str("for (");
+                match &stmt.init {
+                    Some(ForStatementInit::VariableDeclaration(decl)) => {
+                        self.print_variable_declaration(decl, true)
+                    }
+                    // `in` would start a for-in loop
+                    Some(ForStatementInit::Expression(expr)) if contains_in(expr) => {
+                        self.code.push('(');
+                        self.print_expression(expr, Precedence::Lowest);
+                        self.code.push(')');
+                    }
+                    Some(ForStatementInit::Expression(expr)) => {
+                        self.print_expression(expr, Precedence::Lowest)
+                    }
+                    None => {}
+                }
+                self.code.push(';');
+                if let Some(test) = &stmt.test {
+                    self.code.push(' ');
+                    self.print_expression(test, Precedence::Lowest);
+                }
+                self.code.push(';');
+                if let Some(update) = &stmt.update {
+                    self.code.push(' ');
+                    self.print_expression(update, Precedence::Lowest);
+                }
+                self.code.push(')');
+                self.print_body(&stmt.body);
+            }
+            Statement::ForInStatement(stmt) => {
+                self.code.push_str("for (");
+                self.print_for_statement_left(&stmt.left);
+                self.code.push_str(" in ");
+                self.print_expression(&stmt.right, Precedence::Lowest);
+                self.code.push(')');
+                self.print_body(&stmt.body);
+            }
+            Statement::ForOfStatement(stmt) => {
+                self.code
+                    .push_str(if stmt.r#await { "for await (" } else { "for (" });
+                self.print_for_statement_left(&stmt.left);
+                self.code.push_str(" of ");
+                self.print_expression(&stmt.right, Precedence::Assign);
+                self.code.push(')');
+                self.print_body(&stmt.body);
+            }
+            Statement::WhileStatement(stmt) => {
+                self.code.push_str("while (");
+                self.print_expression(&stmt.test, Precedence::Lowest);
+                self.code.push(')');
+                self.print_body(&stmt.body);
+            }
+            Statement::DoWhileStatement(stmt) => {
+                self.code.push_str("do");
+                self.print_body(&stmt.body);
+                self.print_body_end(&stmt.body);
+                self.code.push_str("while (");
+                self.print_expression(&stmt.test, Precedence::Lowest);
+                self.code.push(')');
+                self.print_semicolon();
+            }
+            Statement::BreakStatement(stmt) => {
+                self.code.push_str("break");
+                if let Some(label) = &stmt.label {
+                    self.code.push(' ');
+                    self.code.push_str(label.name);
+                }
+                self.print_semicolon();
+            }
+            Statement::ContinueStatement(stmt) => {
+                self.code.push_str("continue");
+                if let Some(label) = &stmt.label {
+                    self.code.push(' ');
+                    self.code.push_str(label.name);
+                }
+                self.print_semicolon();
+            }
+            Statement::LabeledStatement(stmt) => {
+                self.code.push_str(stmt.label.name);
+                self.code.push_str(": ");
+                self.print_statement_unindented(&stmt.body);
+            }
+            Statement::SwitchStatement(stmt) => self.print_switch_statement(stmt),
+            Statement::WithStatement(stmt) => {
+                self.code.push_str("with (");
+                self.print_expression(&stmt.object, Precedence::Lowest);
+                self.code.push(')');
+                self.print_body(&stmt.body);
+            }
+            Statement::VariableDeclarationStatement(decl) => {
+                self.print_variable_declaration(decl, false);
+                self.print_semicolon();
+            }
+            Statement::FunctionDeclaration(func) => self.print_function(func),
+            Statement::ClassDeclaration(class) => self.print_class(class),
+            Statement::ImportDeclaration(decl) => self.print_import_declaration(decl),
+            Statement::ExportNamedDeclaration(decl) => self.print_export_named_declaration(decl),
+            Statement::ExportDefaultDeclaration(decl) => {
+                self.code.push_str("export default ");
+                match &decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                        self.print_function(func)
+                    }
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                        self.print_class(class)
+                    }
+                    ExportDefaultDeclarationKind::Expression(expr) => {
+                        self.print_expression_not_declaration(expr, Precedence::Assign);
+                        self.print_semicolon();
+                    }
+                }
+            }
+            Statement::ExportAllDeclaration(decl) => {
+                self.code.push_str("export * ");
+                if let Some(exported) = &decl.exported {
+                    self.code.push_str("as ");
+                    self.print_module_export_name(exported);
+                    self.code.push(' ');
+                }
+                self.code.push_str("from ");
+                self.print_string(decl.source.value);
+                self.print_semicolon();
+            }
+            Statement::ThrowStatement(stmt) => {
+                self.code.push_str("throw ");
+                self.print_expression(&stmt.argument, Precedence::Lowest);
+                self.print_semicolon();
+            }
+            Statement::TryStatement(stmt) => {
+                self.code.push_str("try ");
+                self.print_block(&stmt.block.body);
+                if let Some(handler) = &stmt.handler {
+                    self.code.push_str(" catch ");
+                    if let Some(param) = &handler.param {
+                        self.code.push('(');
+                        self.print_binding_pattern(param);
+                        self.code.push_str(") ");
+                    }
+                    self.print_block(&handler.body.body);
+                }
+                if let Some(finalizer) = &stmt.finalizer {
+                    self.code.push_str(" finally ");
+                    self.print_block(&finalizer.body);
+                }
+            }
+            Statement::Error(stmt) => {
+                let text = self.source_text.get(stmt.span.start..stmt.span.end);
+                self.code.push_str(text.unwrap_or_default().trim_end());
+            }
+        }
+    }
+
+    fn print_block(&mut self, body: &[Statement]) {
+        if body.is_empty() {
+            self.code.push_str("{}");
+            return;
+        }
+        self.code.push_str("{\n");
+        self.indent += 1;
+        for stmt in body {
+            self.print_statement(stmt);
+            self.code.push('\n');
+        }
+        self.indent -= 1;
+        self.print_indent();
+        self.code.push('}');
+    }
+
+    /// Print the body of a compound statement, on the same line if it is a block
+    fn print_body(&mut self, body: &Statement) {
+        if let Statement::BlockStatement(block) = body {
+            self.code.push(' ');
+            self.print_block(&block.body);
+        } else {
+            self.code.push('\n');
+            self.indent += 1;
+            self.print_statement(body);
+            self.indent -= 1;
+        }
+    }
+
+    /// Separate a body from a following `else` or `while`
+    fn print_body_end(&mut self, body: &Statement) {
+        if matches!(body, Statement::BlockStatement(_)) {
+            self.code.push(' ');
+        } else {
+            self.code.push('\n');
+            self.print_indent();
+        }
+    }
+
+    fn print_if_statement(&mut self, stmt: &IfStatement) {
+        self.code.push_str("if (");
+        self.print_expression(&stmt.test, Precedence::Lowest);
+        self.code.push(')');
+        let Some(alternate) = &stmt.alternate else {
+            self.print_body(&stmt.consequent);
+            return;
+        };
+        if has_dangling_if(&stmt.consequent) {
+            // Brace the consequent so the `else` is not claimed by the inner `if`
+            self.code.push(' ');
+            self.print_block(std::slice::from_ref(&stmt.consequent));
+            self.code.push(' ');
+        } else {
+            self.print_body(&stmt.consequent);
+            self.print_body_end(&stmt.consequent);
+        }
+        self.code.push_str("else");
+        if let Statement::IfStatement(alternate) = alternate {
+            self.code.push(' ');
+            self.print_if_statement(alternate);
+        } else {
+            self.print_body(alternate);
+        }
+    }
+
+    fn print_switch_statement(&mut self, stmt: &SwitchStatement) {
+        self.code.push_str("switch (");
+        self.print_expression(&stmt.discriminant, Precedence::Lowest);
+        self.code.push_str(") {\n");
+        self.indent += 1;
+        for case in &stmt.cases {
+            self.print_indent();
+            match &case.test {
+                Some(test) => {
+                    self.code.push_str("case ");
+                    self.print_expression(test, Precedence::Lowest);
+                    self.code.push(':');
+                }
+                None => self.code.push_str("default:"),
+            }
+            self.code.push('\n');
+            self.indent += 1;
+            for stmt in &case.consequent {
+                self.print_statement(stmt);
+                self.code.push('\n');
+            }
+            self.indent -= 1;
+        }
+        self.indent -= 1;
+        self.print_indent();
+        self.code.push('}');
+    }
+
+    fn print_expression_statement(&mut self, expr: &Expression) {
+        let start = self.code.len();
+        let wrapped = self.print_expression_not_declaration(expr, Precedence::Lowest);
+        if !wrapped
+            && !self.options.semicolons
+            && self.code[start..].starts_with(['(', '[', '+', '-', '/'])
+        {
+            // Without semicolons these would continue the previous statement
+            self.code.insert(start, ';');
+        }
+    }
+
+    /// Print `expr` where a leading `{`, `function`, `async function` or `class` would start a
+    /// block or a declaration instead, returning whether it had to be parenthesized
+    fn print_expression_not_declaration(&mut self, expr: &Expression, min: Precedence) -> bool {
+        let start = self.code.len();
+        self.print_expression(expr, min);
+        let printed = &self.code[start..];
+        let starts_with_keyword = |keyword: &str| {
+            printed.strip_prefix(keyword).is_some_and(|rest| {
+                !rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            })
+        };
+        let wrap = printed.starts_with('{')
+            || starts_with_keyword("function")
+            || printed.starts_with("async function")
+            || starts_with_keyword("class");
+        if wrap {
+            self.code.insert(start, '(');
+            self.code.push(')');
+        }
+        wrap
+    }
+
+    fn print_import_declaration(&mut self, decl: &ImportDeclaration) {
+        self.code.push_str("import ");
+        let mut named = vec![];
+        for specifier in &decl.specifiers {
+            match specifier {
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                    self.code.push_str(specifier.local.name);
+                    self.code.push_str(", ");
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                    self.code.push_str("* as ");
+                    self.code.push_str(specifier.local.name);
+                    self.code.push_str(", ");
+                }
+                ImportDeclarationSpecifier::ImportSpecifier(specifier) => named.push(specifier),
+            }
+        }
+        if !named.is_empty() {
+            self.code.push_str("{ ");
+            self.print_list(&named, |p, specifier| {
+                let renamed = match &specifier.imported {
+                    ModuleExportName::IdentifierName(name) => name.name != specifier.local.name,
+                    ModuleExportName::StringLiteral(_) => true,
+                };
+                if renamed {
+                    p.print_module_export_name(&specifier.imported);
+                    p.code.push_str(" as ");
+                }
+                p.code.push_str(specifier.local.name);
+            });
+            self.code.push_str(" }, ");
+        }
+        if let Some(specifiers) = self.code.strip_suffix(", ") {
+            self.code.truncate(specifiers.len());
+            self.code.push_str(" from ");
+        }
+        self.print_string(decl.source.value);
+        self.print_semicolon();
+    }
+
+    fn print_export_named_declaration(&mut self, decl: &ExportNamedDeclaration) {
+        self.code.push_str("export ");
+        if let Some(declaration) = &decl.declaration {
+            // the indentation is printed already
+            let indent = std::mem::take(&mut self.indent);
+            self.print_statement(declaration);
+            self.indent = indent;
+            return;
+        }
+        self.code.push('{');
+        if !decl.specifiers.is_empty() {
+            self.code.push(' ');
+            self.print_list(&decl.specifiers, |p, specifier| {
+                p.print_module_export_name(&specifier.local);
+                if !same_module_export_name(&specifier.local, &specifier.exported) {
+                    p.code.push_str(" as ");
+                    p.print_module_export_name(&specifier.exported);
+                }
+            });
+            self.code.push(' ');
+        }
+        self.code.push('}');
+        if let Some(source) = &decl.source {
+            self.code.push_str(" from ");
+            self.print_string(source.value);
+        }
+        self.print_semicolon();
+    }
+
+    fn print_module_export_name(&mut self, name: &ModuleExportName) {
+        match name {
+            ModuleExportName::IdentifierName(name) => self.code.push_str(name.name),
+            ModuleExportName::StringLiteral(lit) => self.print_string(lit.value),
+        }
+    }
+
+    /// `no_in` keeps a top-level `in` of an initializer from ending the head of a `for` statement
+    fn print_variable_declaration(&mut self, decl: &VariableDeclaration, no_in: bool) {
+        self.code.push_str(match decl.kind {
+            VariableDeclarationKind::Var => "var ",
+            VariableDeclarationKind::Let => "let ",
+            VariableDeclarationKind::Const => "const ",
+        });
+        self.print_list(&decl.declarations, |p, declarator| {
+            p.print_binding_pattern(&declarator.id);
+            match &declarator.init {
+                Some(init) if no_in && contains_in(init) => {
+                    p.code.push_str(" = (");
+                    p.print_expression(init, Precedence::Lowest);
+                    p.code.push(')');
+                }
+                Some(init) => {
+                    p.code.push_str(" = ");
+                    p.print_expression(init, Precedence::Assign);
+                }
+                None => {}
+            }
+        });
+    }
+
+    fn print_for_statement_left(&mut self, left: &ForStatementLeft) {
+        match left {
+            ForStatementLeft::VariableDeclaration(decl) => {
+                self.print_variable_declaration(decl, false)
+            }
+            ForStatementLeft::AssignmentTarget(target) => self.print_assignment_target(target),
+        }
+    }
+
+    fn print_function(&mut self, func: &Function) {
+        if func.is_async {
+            self.code.push_str("async ");
+        }
+        self.code.push_str("function");
+        if func.is_generator {
+            self.code.push('*');
+        }
+        if let Some(id) = &func.id {
+            self.code.push(' ');
+            self.code.push_str(id.name);
+        }
+        self.print_function_rest(func);
+    }
+
+    /// Print the parameters and body of a function
+    fn print_function_rest(&mut self, func: &Function) {
+        self.print_formal_parameters(&func.params);
+        self.code.push(' ');
+        match &func.body {
+            Some(body) => self.print_block(&body.statements),
+            None => self.code.push_str("{}"),
+        }
+    }
+
+    /// Print a method or accessor after `static`, `get` or `set`
+    fn print_method(&mut self, key: &PropertyKey, func: &Function) {
+        if func.is_async {
+            self.code.push_str("async ");
+        }
+        if func.is_generator {
+            self.code.push('*');
+        }
+        self.print_property_key(key);
+        self.print_function_rest(func);
+    }
+
+    fn print_class(&mut self, class: &Class) {
+        self.code.push_str("class");
+        if let Some(id) = &class.id {
+            self.code.push(' ');
+            self.code.push_str(id.name);
+        }
+        if let Some(super_class) = &class.super_class {
+            self.code.push_str(" extends ");
+            self.print_expression(super_class, Precedence::New);
+        }
+        if class.body.body.is_empty() {
+            self.code.push_str(" {}");
+            return;
+        }
+        self.code.push_str(" {\n");
+        self.indent += 1;
+        for element in &class.body.body {
+            self.print_indent();
+            self.print_class_element(element);
+            self.code.push('\n');
+        }
+        self.indent -= 1;
+        self.print_indent();
+        self.code.push('}');
+    }
+
+    fn print_class_element(&mut self, element: &ClassElement) {
+        match element {
+            ClassElement::MethodDefinition(method) => {
+                if method.r#static {
+                    self.code.push_str("static ");
+                }
+                match method.kind {
+                    MethodDefinitionKind::Get => self.code.push_str("get "),
+                    MethodDefinitionKind::Set => self.code.push_str("set "),
+                    MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => {}
+                }
+                self.print_method(&method.key, &method.value);
+            }
+            ClassElement::PropertyDefinition(prop) => {
+                if prop.r#static {
+                    self.code.push_str("static ");
+                }
+                self.print_property_key(&prop.key);
+                if let Some(value) = &prop.value {
+                    self.code.push_str(" = ");
+                    self.print_expression(value, Precedence::Assign);
+                }
+                self.print_semicolon();
+            }
+            ClassElement::StaticBlock(block) => {
+                self.code.push_str("static ");
+                self.print_block(&block.body);
+            }
+        }
+    }
+}
+
+// expressions:
+impl JsCodegen<'_> {
+    /// Print `expr`, parenthesized if it binds looser than `min`
+    fn print_expression(&mut self, expr: &Expression, min: Precedence) {
+        let wrap = expression_precedence(expr) < min;
+        if wrap {
+            self.code.push('(');
+        }
+        self.print_expression_inner(expr);
+        if wrap {
+            self.code.push(')');
+        }
+    }
+
+    fn print_expression_inner(&mut self, expr: &Expression) {
+        match expr {
+            Expression::BooleanLiteral(lit) => {
+                self.code.push_str(if lit.value { "true" } else { "false" })
+            }
+            Expression::NullLiteral(_) => self.code.push_str("null"),
+            Expression::NumericLiteral(lit) => self.code.push_str(lit.raw),
+            Expression::StringLiteral(lit) => self.print_string(lit.value),
+            Expression::TemplateLiteral(template) => self.print_template_literal(template),
+            Expression::RegExpLiteral(lit) => {
+                self.code.push('/');
+                self.code.push_str(lit.pattern);
+                self.code.push('/');
+                self.code.push_str(lit.flags);
+            }
+            Expression::TaggedTemplateExpression(expr) => {
+                self.print_member_object(&expr.tag);
+                self.print_template_literal(&expr.quasi);
+            }
+            Expression::Identifier(ident) => self.code.push_str(ident.name),
+            Expression::SequenceExpression(seq) => {
+                self.print_list(&seq.expressions, |p, expr| {
+                    p.print_expression(expr, Precedence::Assign)
+                });
+            }
+            Expression::BinaryExpression(expr) => {
+                let precedence = expr.operator.precedence();
+                let (left, right) = if precedence.is_right_associative() {
+                    // `-a ** b` is a syntax error, so unary operands need parentheses too
+                    (Precedence::Postfix, precedence)
+                } else {
+                    (precedence, tighter(precedence))
+                };
+                self.print_expression(&expr.left, left);
+                self.code.push(' ');
+                self.code.push_str(expr.operator.as_str());
+                self.code.push(' ');
+                self.print_expression(&expr.right, right);
+            }
+            Expression::UnaryExpression(expr) => {
+                let op = expr.operator.as_str();
+                self.code.push_str(op);
+                if expr.operator.is_keyword() {
+                    self.code.push(' ');
+                }
+                let start = self.code.len();
+                self.print_expression(&expr.argument, Precedence::Prefix);
+                // `- -a` must not turn into `--a`
+                if !expr.operator.is_keyword() && self.code[start..].starts_with(op) {
+                    self.code.insert(start, ' ');
+                }
+            }
+            Expression::UpdateExpression(expr) => {
+                if expr.prefix {
+                    self.code.push_str(expr.operator.as_str());
+                }
+                self.print_assignment_target(&expr.argument);
+                if !expr.prefix {
+                    self.code.push_str(expr.operator.as_str());
+                }
+            }
+            Expression::LogicalExpression(expr) => {
+                let precedence = expr.operator.precedence();
+                // `??` cannot be mixed with `||` and `&&` without parentheses
+                let (left, right) = if expr.operator == LogicalOperator::Coalesce {
+                    (Precedence::BitwiseOr, Precedence::BitwiseOr)
+                } else {
+                    (precedence, tighter(precedence))
+                };
+                self.print_expression(&expr.left, left);
+                self.code.push(' ');
+                self.code.push_str(expr.operator.as_str());
+                self.code.push(' ');
+                self.print_expression(&expr.right, right);
+            }
+            Expression::ConditionalExpression(expr) => {
+                self.print_expression(&expr.test, Precedence::NullishCoalescing);
+                self.code.push_str(" ? ");
+                self.print_expression(&expr.consequent, Precedence::Assign);
+                self.code.push_str(" : ");
+                self.print_expression(&expr.alternate, Precedence::Assign);
+            }
+            Expression::AssignmentExpression(expr) => {
+                self.print_assignment_target(&expr.left);
+                self.code.push(' ');
+                self.code.push_str(expr.operator.as_str());
+                self.code.push(' ');
+                self.print_expression(&expr.right, Precedence::Assign);
+            }
+            Expression::ArrayExpression(array) => {
+                self.code.push('[');
+                self.print_list(&array.elements, |p, element| match element {
+                    ArrayExpressionElement::Elision(_) => {}
+                    ArrayExpressionElement::Expression(expr) => {
+                        p.print_expression(expr, Precedence::Assign)
+                    }
+                    ArrayExpressionElement::SpreadElement(spread) => p.print_spread_element(spread),
+                });
+                // A trailing hole needs its own comma: `[a, ,]`
+                if let Some(ArrayExpressionElement::Elision(_)) = array.elements.last() {
+                    self.code.push(',');
+                }
+                self.code.push(']');
+            }
+            Expression::ObjectExpression(object) => self.print_object_expression(object),
+            Expression::StaticMemberExpression(member) => {
+                self.print_member_object(&member.object);
+                self.code.push_str(if member.optional { "?." } else { "." });
+                self.code.push_str(member.property.name);
+            }
+            Expression::ComputedMemberExpression(member) => {
+                self.print_member_object(&member.object);
+                self.code
+                    .push_str(if member.optional { "?.[" } else { "[" });
+                self.print_expression(&member.expression, Precedence::Lowest);
+                self.code.push(']');
+            }
+            Expression::ChainExpression(chain) => self.print_expression_inner(&chain.expression),
+            Expression::CallExpression(call) => {
+                self.print_expression(&call.callee, Precedence::Call);
+                if call.optional {
+                    self.code.push_str("?.");
+                }
+                self.code.push('(');
+                self.print_list(&call.arguments, Self::print_argument);
+                self.code.push(')');
+            }
+            Expression::NewExpression(expr) => {
+                self.code.push_str("new ");
+                if has_call(&expr.callee) {
+                    // `new f().C` would call `f` with `new`
+                    self.code.push('(');
+                    self.print_expression(&expr.callee, Precedence::Lowest);
+                    self.code.push(')');
+                } else {
+                    self.print_expression(&expr.callee, Precedence::Member);
+                }
+                if !expr.arguments.is_empty() {
+                    self.code.push('(');
+                    self.print_list(&expr.arguments, Self::print_argument);
+                    self.code.push(')');
+                }
+            }
+            Expression::ThisExpression(_) => self.code.push_str("this"),
+            Expression::FunctionExpression(func) => self.print_function(func),
+            Expression::ClassExpression(class) => self.print_class(class),
+            Expression::Super(_) => self.code.push_str("super"),
+            Expression::ImportExpression(expr) => {
+                self.code.push_str("import(");
+                self.print_expression(&expr.source, Precedence::Assign);
+                self.code.push(')');
+            }
+            Expression::ArrowFunctionExpression(arrow) => self.print_arrow_function(arrow),
+            Expression::MetaProperty(meta) => {
+                self.code.push_str(meta.meta.name);
+                self.code.push('.');
+                self.code.push_str(meta.property.name);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.print_expression_inner(&paren.expression)
+            }
+            Expression::AwaitExpression(expr) => {
+                self.code.push_str("await ");
+                self.print_expression(&expr.argument, Precedence::Prefix);
+            }
+            Expression::YieldExpression(expr) => {
+                self.code.push_str("yield");
+                if expr.delegate {
+                    self.code.push('*');
+                }
+                if let Some(argument) = &expr.argument {
+                    self.code.push(' ');
+                    self.print_expression(argument, Precedence::Assign);
+                }
+            }
+        }
+    }
+
+    fn print_arrow_function(&mut self, arrow: &ArrowFunctionExpression) {
+        if arrow.is_async {
+            self.code.push_str("async ");
+        }
+        match (arrow.params.params.as_slice(), &arrow.params.rest) {
+            ([BindingPattern::BindingIdentifier(ident)], None) => self.code.push_str(ident.name),
+            _ => self.print_formal_parameters(&arrow.params),
+        }
+        self.code.push_str(" => ");
+        match arrow.body.statements.as_slice() {
+            [Statement::ExpressionStatement(stmt)] if arrow.expression => {
+                let start = self.code.len();
+                self.print_expression(&stmt.expression, Precedence::Assign);
+                // `{` would start a block body instead
+                if self.code[start..].starts_with('{') {
+                    self.code.insert(start, '(');
+                    self.code.push(')');
+                }
+            }
+            statements => self.print_block(statements),
+        }
+    }
+
+    fn print_object_expression(&mut self, object: &ObjectExpression) {
+        if object.properties.is_empty() {
+            self.code.push_str("{}");
+            return;
+        }
+        // Methods and accessors have block bodies, so every property gets its own line
+        let has_methods = object.properties.iter().any(|property| {
+            matches!(property, ObjectPropertyKind::ObjectProperty(property)
+                if property.kind != PropertyKind::Init)
+        });
+        if !has_methods {
+            self.code.push_str("{ ");
+            self.print_list(&object.properties, Self::print_object_property);
+            self.code.push_str(" }");
+            return;
+        }
+        self.code.push_str("{\n");
+        self.indent += 1;
+        for (i, property) in object.properties.iter().enumerate() {
+            self.print_indent();
+            self.print_object_property(property);
+            if i + 1 < object.properties.len() {
+                self.code.push(',');
+            }
+            self.code.push('\n');
+        }
+        self.indent -= 1;
+        self.print_indent();
+        self.code.push('}');
+    }
+
+    fn print_object_property(&mut self, property: &ObjectPropertyKind) {
+        let property = match property {
+            ObjectPropertyKind::ObjectProperty(property) => property,
+            ObjectPropertyKind::SpreadProperty(spread) => return self.print_spread_element(spread),
+        };
+        match &property.value {
+            Expression::FunctionExpression(func) if property.kind != PropertyKind::Init => {
+                match property.kind {
+                    PropertyKind::Get => self.code.push_str("get "),
+                    PropertyKind::Set => self.code.push_str("set "),
+                    PropertyKind::Init | PropertyKind::Method => {}
+                }
+                self.print_method(&property.key, func);
+            }
+            value if property.shorthand => self.print_expression(value, Precedence::Assign),
+            value => {
+                self.print_property_key(&property.key);
+                self.code.push_str(": ");
+                self.print_expression(value, Precedence::Assign);
+            }
+        }
+    }
+
+    fn print_template_literal(&mut self, template: &TemplateLiteral) {
+        self.code.push('`');
+        for (i, quasi) in template.quasis.iter().enumerate() {
+            if i > 0 {
+                self.code.push('}');
+            }
+            self.code.push_str(quasi.value.raw);
+            if let Some(expr) = template.expressions.get(i) {
+                self.code.push_str("${");
+                self.print_expression(expr, Precedence::Lowest);
+            }
+        }
+        self.code.push('`');
+    }
+
+    fn print_formal_parameters(&mut self, params: &FormalParameters) {
+        self.code.push('(');
+        self.print_list(&params.params, Self::print_binding_pattern);
+        if let Some(rest) = &params.rest {
+            if !params.params.is_empty() {
+                self.code.push_str(", ");
+            }
+            self.code.push_str("...");
+            self.print_binding_pattern(&rest.argument);
+        }
+        self.code.push(')');
+    }
+
+    fn print_argument(&mut self, argument: &Argument) {
+        match argument {
+            Argument::SpreadElement(spread) => self.print_spread_element(spread),
+            Argument::Expression(expr) => self.print_expression(expr, Precedence::Assign),
+        }
+    }
+
+    fn print_spread_element(&mut self, spread: &SpreadElement) {
+        self.code.push_str("...");
+        self.print_expression(&spread.argument, Precedence::Assign);
+    }
+
+    fn print_member_object(&mut self, object: &Expression) {
+        // `1.toString()` would lex `1.` as the number
+        if let Expression::NumericLiteral(lit) = without_parentheses(object) {
+            if lit.raw.bytes().all(|b| b.is_ascii_digit()) {
+                self.code.push('(');
+                self.code.push_str(lit.raw);
+                self.code.push(')');
+                return;
+            }
+        }
+        self.print_expression(object, Precedence::Call);
+    }
+
+    fn print_assignment_target(&mut self, target: &AssignmentTarget) {
+        match target {
+            AssignmentTarget::Identifier(ident) => self.code.push_str(ident.name),
+            AssignmentTarget::StaticMemberExpression(member) => {
+                self.print_member_object(&member.object);
+                self.code.push('.');
+                self.code.push_str(member.property.name);
+            }
+            AssignmentTarget::ComputedMemberExpression(member) => {
+                self.print_member_object(&member.object);
+                self.code.push('[');
+                self.print_expression(&member.expression, Precedence::Lowest);
+                self.code.push(']');
+            }
+            AssignmentTarget::ArrayAssignmentTarget(array) => {
+                self.code.push('[');
+                self.print_list(&array.elements, |p, element| {
+                    if let Some(element) = element {
+                        p.print_assignment_target_maybe_default(element);
+                    }
+                });
+                match &array.rest {
+                    Some(rest) => {
+                        if !array.elements.is_empty() {
+                            self.code.push_str(", ");
+                        }
+                        self.code.push_str("...");
+                        self.print_assignment_target(&rest.target);
+                    }
+                    // A trailing hole needs its own comma: `[a, ,]`
+                    None if matches!(array.elements.last(), Some(None)) => self.code.push(','),
+                    None => {}
+                }
+                self.code.push(']');
+            }
+            AssignmentTarget::ObjectAssignmentTarget(object) => {
+                if object.properties.is_empty() && object.rest.is_none() {
+                    self.code.push_str("{}");
+                    return;
+                }
+                self.code.push_str("{ ");
+                self.print_list(&object.properties, |p, property| {
+                    p.print_property_key(&property.key);
+                    p.code.push_str(": ");
+                    p.print_assignment_target_maybe_default(&property.value);
+                });
+                if let Some(rest) = &object.rest {
+                    if !object.properties.is_empty() {
+                        self.code.push_str(", ");
+                    }
+                    self.code.push_str("...");
+                    self.print_assignment_target(&rest.target);
+                }
+                self.code.push_str(" }");
+            }
+        }
+    }
+
+    fn print_assignment_target_maybe_default(&mut self, target: &AssignmentTargetMaybeDefault) {
+        match target {
+            AssignmentTargetMaybeDefault::AssignmentTarget(target) => {
+                self.print_assignment_target(target)
+            }
+            AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => {
+                self.print_assignment_target(&target.binding);
+                self.code.push_str(" = ");
+                self.print_expression(&target.init, Precedence::Assign);
+            }
+        }
+    }
+
+    fn print_binding_pattern(&mut self, pattern: &BindingPattern) {
+        match pattern {
+            BindingPattern::BindingIdentifier(ident) => self.code.push_str(ident.name),
+            BindingPattern::ObjectPattern(object) => {
+                if object.properties.is_empty() && object.rest.is_none() {
+                    self.code.push_str("{}");
+                    return;
+                }
+                self.code.push_str("{ ");
+                self.print_list(&object.properties, |p, property| {
+                    if !property.shorthand {
+                        p.print_property_key(&property.key);
+                        p.code.push_str(": ");
+                    }
+                    p.print_binding_pattern(&property.value);
+                });
+                if let Some(rest) = &object.rest {
+                    if !object.properties.is_empty() {
+                        self.code.push_str(", ");
+                    }
+                    self.code.push_str("...");
+                    self.print_binding_pattern(&rest.argument);
+                }
+                self.code.push_str(" }");
+            }
+            BindingPattern::ArrayPattern(array) => {
+                self.code.push('[');
+                self.print_list(&array.elements, |p, element| {
+                    if let Some(element) = element {
+                        p.print_binding_pattern(element);
+                    }
+                });
+                match &array.rest {
+                    Some(rest) => {
+                        if !array.elements.is_empty() {
+                            self.code.push_str(", ");
+                        }
+                        self.code.push_str("...");
+                        self.print_binding_pattern(&rest.argument);
+                    }
+                    // A trailing hole needs its own comma: `[a, ,]`
+                    None if matches!(array.elements.last(), Some(None)) => self.code.push(','),
+                    None => {}
+                }
+                self.code.push(']');
+            }
+            BindingPattern::AssignmentPattern(pattern) => {
+                self.print_binding_pattern(&pattern.left);
+                self.code.push_str(" = ");
+                self.print_expression(&pattern.right, Precedence::Assign);
+            }
+        }
+    }
+
+    fn print_property_key(&mut self, key: &PropertyKey) {
+        match key {
+            PropertyKey::IdentifierName(ident) => self.code.push_str(ident.name),
+            PropertyKey::StringLiteral(lit) => self.print_string(lit.value),
+            PropertyKey::NumericLiteral(lit) => self.code.push_str(lit.raw),
+            PropertyKey::Computed(expr) => {
+                self.code.push('[');
+                self.print_expression(expr, Precedence::Assign);
+                self.code.push(']');
+            }
+        }
+    }
+}
+
+fn expression_precedence(expr: &Expression) -> Precedence {
+    match expr {
+        Expression::SequenceExpression(_) => Precedence::Comma,
+        Expression::YieldExpression(_) => Precedence::Yield,
+        Expression::AssignmentExpression(_) | Expression::ArrowFunctionExpression(_) => {
+            Precedence::Assign
+        }
+        Expression::ConditionalExpression(_) => Precedence::Conditional,
+        Expression::BinaryExpression(expr) => expr.operator.precedence(),
+        Expression::LogicalExpression(expr) => expr.operator.precedence(),
+        Expression::UnaryExpression(_) | Expression::AwaitExpression(_) => Precedence::Prefix,
+        Expression::UpdateExpression(expr) if expr.prefix => Precedence::Prefix,
+        Expression::UpdateExpression(_) => Precedence::Postfix,
+        Expression::CallExpression(_) => Precedence::Call,
+        // `(a?.b).c` does not short-circuit `.c`, the chain cannot be extended unparenthesized
+        Expression::ChainExpression(_) => Precedence::Postfix,
+        // `new C` without arguments cannot be called or accessed without parentheses
+        Expression::NewExpression(expr) if expr.arguments.is_empty() => Precedence::New,
+        Expression::ParenthesizedExpression(paren) => expression_precedence(&paren.expression),
+        Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::NumericLiteral(_)
+        | Expression::StringLiteral(_)
+        | Expression::TemplateLiteral(_)
+        | Expression::RegExpLiteral(_)
+        | Expression::Identifier(_)
+        | Expression::ArrayExpression(_)
+        | Expression::ObjectExpression(_)
+        | Expression::StaticMemberExpression(_)
+        | Expression::ComputedMemberExpression(_)
+        | Expression::NewExpression(_)
+        | Expression::TaggedTemplateExpression(_)
+        | Expression::ThisExpression(_)
+        | Expression::ImportExpression(_)
+        | Expression::MetaProperty(_)
+        | Expression::FunctionExpression(_)
+        | Expression::ClassExpression(_)
+        | Expression::Super(_) => Precedence::Member,
+    }
+}
+
+fn same_module_export_name(a: &ModuleExportName, b: &ModuleExportName) -> bool {
+    match (a, b) {
+        (ModuleExportName::IdentifierName(a), ModuleExportName::IdentifierName(b)) => {
+            a.name == b.name
+        }
+        (ModuleExportName::StringLiteral(a), ModuleExportName::StringLiteral(b)) => {
+            a.value == b.value
+        }
+        _ => false,
+    }
+}
+
+/// Whether a call is reachable through the objects of a member chain
+fn has_call(expr: &Expression) -> bool {
+    match expr {
+        Expression::CallExpression(_) => true,
+        Expression::StaticMemberExpression(member) => has_call(&member.object),
+        Expression::ComputedMemberExpression(member) => has_call(&member.object),
+        Expression::TaggedTemplateExpression(expr) => has_call(&expr.tag),
+        Expression::ParenthesizedExpression(paren) => has_call(&paren.expression),
+        _ => false,
+    }
+}
+
+fn without_parentheses<'b, 'a>(expr: &'b Expression<'a>) -> &'b Expression<'a> {
+    match expr {
+        Expression::ParenthesizedExpression(paren) => without_parentheses(&paren.expression),
+        expr => expr,
+    }
+}
+
+/// The next tighter level, used for the right operand of left-associative operators
+fn tighter(precedence: Precedence) -> Precedence {
+    use Precedence::*;
+    const LEVELS: [Precedence; 23] = [
+        Lowest,
+        Comma,
+        Spread,
+        Yield,
+        Assign,
+        Conditional,
+        NullishCoalescing,
+        LogicalOr,
+        LogicalAnd,
+        BitwiseOr,
+        BitwiseXor,
+        BitwiseAnd,
+        Equals,
+        Compare,
+        Shift,
+        Add,
+        Multiply,
+        Exponentiation,
+        Prefix,
+        Postfix,
+        New,
+        Call,
+        Member,
+    ];
+    LEVELS[(precedence as usize + 1).min(LEVELS.len() - 1)]
+}
+
+/// Whether `stmt` ends with an `if` that has no `else` of its own
+fn has_dangling_if(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::IfStatement(stmt) => match &stmt.alternate {
+            Some(alternate) => has_dangling_if(alternate),
+            None => true,
+        },
+        Statement::ForStatement(stmt) => has_dangling_if(&stmt.body),
+        Statement::ForInStatement(stmt) => has_dangling_if(&stmt.body),
+        Statement::ForOfStatement(stmt) => has_dangling_if(&stmt.body),
+        Statement::WhileStatement(stmt) => has_dangling_if(&stmt.body),
+        Statement::WithStatement(stmt) => has_dangling_if(&stmt.body),
+        Statement::LabeledStatement(stmt) => has_dangling_if(&stmt.body),
+        _ => false,
+    }
+}
+
+/// Whether `expr` prints an `in` operator that is not nested in brackets or a function body
+fn contains_in(expr: &Expression) -> bool {
+    match expr {
+        Expression::BinaryExpression(expr) => {
+            expr.operator == BinaryOperator::In
+                || contains_in(&expr.left)
+                || contains_in(&expr.right)
+        }
+        Expression::LogicalExpression(expr) => contains_in(&expr.left) || contains_in(&expr.right),
+        Expression::ConditionalExpression(expr) => {
+            contains_in(&expr.test) || contains_in(&expr.alternate)
+        }
+        Expression::AssignmentExpression(expr) => contains_in(&expr.right),
+        Expression::SequenceExpression(expr) => expr.expressions.iter().any(contains_in),
+        Expression::UnaryExpression(expr) => contains_in(&expr.argument),
+        Expression::AwaitExpression(expr) => contains_in(&expr.argument),
+        Expression::YieldExpression(expr) => expr.argument.as_ref().is_some_and(contains_in),
+        Expression::ParenthesizedExpression(paren) => contains_in(&paren.expression),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::Parser;
+    use proptest::prelude::*;
+    use serde_json::Value;
+
+    // Sources are parsed as modules, which accept every script the printer is tested with
+    fn print(source: &str, options: JsCodegenOptions) -> String {
+        let mut parser = Parser::new(source);
+        let program = parser.parse_module().unwrap();
+        JsCodegen::new(options).build(&program)
+    }
+
+    fn print_default(source: &str) -> String {
+        print(source, JsCodegenOptions::default())
+    }
+
+    /// The serialized AST without spans and parentheses, which the printer may change
+    fn normalized_ast(source: &str) -> std::result::Result<Value, String> {
+        fn normalize(value: Value) -> Value {
+            match value {
+                Value::Object(mut map) => {
+                    if let Some(Value::Object(paren)) = map.remove("ParenthesizedExpression") {
+                        return normalize(paren["expression"].clone());
+                    }
+                    map.remove("span");
+                    map.remove("source_text");
+                    map.remove("trailing_comma");
+                    Value::Object(map.into_iter().map(|(k, v)| (k, normalize(v))).collect())
+                }
+                Value::Array(items) => Value::Array(items.into_iter().map(normalize).collect()),
+                value => value,
+            }
+        }
+        let mut parser = Parser::new(source);
+        let program = parser.parse_module().map_err(|e| format!("{e:?}"))?;
+        Ok(normalize(serde_json::to_value(&program).unwrap()))
+    }
+
+    fn assert_round_trip(source: &str) {
+        let printed = print_default(source);
+        let ast = normalized_ast(source).unwrap();
+        let reparsed = normalized_ast(&printed)
+            .unwrap_or_else(|e| panic!("failed to reparse {printed:?}: {e}"));
+        assert_eq!(ast, reparsed, "{source:?} printed as {printed:?}");
+        assert_eq!(printed, print_default(&printed));
+    }
+
+    #[test]
+    fn print_statements_test() {
+        let source = "function f(a, b) { if (a) return b; else { while (b) b -= 1 } }";
+        assert_eq!(
+            print_default(source),
+            "function f(a, b) {\n  if (a)\n    return b;\n  else {\n    while (b)\n      b -= 1;\n  }\n}\n"
+        );
+        let source = "switch (a) { case 1: break; default: a = [1, , 2,,] }";
+        assert_eq!(
+            print_default(source),
+            "switch (a) {\n  case 1:\n    break;\n  default:\n    a = [1, , 2, ,];\n}\n"
+        );
+    }
+
+    #[test]
+    fn minimal_parentheses_test() {
+        assert_eq!(print_default("((a + b)) * (c);"), "(a + b) * c;\n");
+        assert_eq!(print_default("a - (b - c);"), "a - (b - c);\n");
+        assert_eq!(print_default("(a - b) - c;"), "a - b - c;\n");
+        assert_eq!(print_default("a = (b = c);"), "a = b = c;\n");
+        assert_eq!(print_default("f((a, b), c);"), "f((a, b), c);\n");
+        assert_eq!(print_default("- (-a);"), "- -a;\n");
+        assert_eq!(print_default("({ a: 1 }).a;"), "({ a: 1 }.a);\n");
+        assert_eq!(print_default("(1).toString();"), "(1).toString();\n");
+        assert_eq!(
+            print_default("new (a().b)(new c.d(), (new e).f);"),
+            "new (a().b)(new c.d, (new e).f);\n"
+        );
+        assert_eq!(
+            print_default("new (new a)(b); (new c)();"),
+            "new (new a)(b);\n(new c)();\n"
+        );
+        assert_eq!(
+            print_default("(a ? b : c) ? d : (e ? f : g);"),
+            "(a ? b : c) ? d : e ? f : g;\n"
+        );
+        assert_eq!(
+            print_default("a ? (b, c) : (d = e);"),
+            "a ? (b, c) : d = e;\n"
+        );
+        assert_eq!(
+            print_default("(a?.b).c; (a?.b)(); (a?.[b])?.c; -(a?.b);"),
+            "(a?.b).c;\n(a?.b)();\n(a?.[b])?.c;\n-a?.b;\n"
+        );
+        assert_eq!(
+            print_default("(-a) ** b; (a ** b) ** c; a ** (b ** c); a ** -b;"),
+            "(-a) ** b;\n(a ** b) ** c;\na ** b ** c;\na ** -b;\n"
+        );
+        assert_eq!(
+            print_default("(a ?? b) || c; a ?? (b && c); (a ?? b) ?? c;"),
+            "(a ?? b) || c;\na ?? (b && c);\n(a ?? b) ?? c;\n"
+        );
+        assert_eq!(
+            print_default("(await a) ** b; await (a + b); (async function () {});"),
+            "(await a) ** b;\nawait (a + b);\n(async function() {});\n"
+        );
+    }
+
+    #[test]
+    fn options_test() {
+        let options = JsCodegenOptions {
+            indent: Indent::Tab,
+            quote: Quote::Double,
+            semicolons: false,
+        };
+        assert_eq!(
+            print(r#"if (a) { b('it\'s', "\"q\"") } (c || d).e"#, options),
+            "if (a) {\n\tb(\"it's\", \"\\\"q\\\"\")\n}\n;(c || d).e\n"
+        );
+        let options = JsCodegenOptions {
+            quote: Quote::Single,
+            ..Default::default()
+        };
+        assert_eq!(print(r#"a = "it's";"#, options), "a = 'it\\'s';\n");
+    }
+
+    #[test]
+    fn print_error_statement_test() {
+        let parser = Parser::new("a = ;\nb;");
+        let program = parser.parse_recovering().program;
+        let printed = JsCodegen::new(JsCodegenOptions::default()).build(&program);
+        assert_eq!(printed, "a = ;\nb;\n");
+    }
+
+    #[test]
+    fn round_trip_statements_test() {
+        for source in [
+            "var a = 1; let b; const c = 'c';",
+            "var a, b = (1, 2), c = b = 3; for (let i = 0, j; i < j; i++) {}",
+            "for (var i = 0; i < 10; i += 1) { continue }",
+            "for (i = 0; i < n; i++) --a[i].b;",
+            "for (;;) {}",
+            "for ((a in b); c; d) {} for (var i = (a in b) ? 1 : 2;;) {}",
+            "for (const k in o) f(k); for (a.b of [c, d]) {} for await (let x of y) {}",
+            "do a(); while (b)",
+            "if (a) if (b) c(); else d();",
+            "try { throw a, b } catch (e) { f(e) } finally {}",
+            "function f() { try { g() } catch { throw new Error(this, new.target) } }",
+            "x = { a: 1, 'b': [1, , 2], 3: (1, 2) };",
+            "f(function (a) { return a }, (a, b) => a + b, () => ({}), a => { });",
+            "x = a ? (b) => c : function g() {};",
+            "class A extends (B, C) { static x = 1; y; get z() { return super.z } static {} }",
+            "(class {});\nclass B extends A { constructor() { super() } static set 'a'(v) {} }",
+            "import 'a'; import b, * as c from 'd'; import e, { f, default as g, 'h' as i } from 'j';",
+            "export var a = 1; export function b() {} export class C {} export { a as d, b, a as 'e' };",
+            "export { default } from 'a'; export * from 'b'; export * as c from 'd';",
+            "export default (function () {}); x = import('a').then(() => import.meta);",
+            "export default class extends A {}",
+            "export default (a, b);",
+            "const { a, b: [c, , d = 1, ...e], ...f } = g; let [] = h, {} = i;",
+            "function f({ a = 1 }, [b, ,], c = 2) {} x = ([a], { b: c }) => a;",
+            "[a, b.c, [d] = e] = [1, 2]; ({ a: b[0], c: { d: d } = e } = f);",
+            "for ([a, b] of c) {} for (const { a } in b) {} try {} catch ({ a, b: [c] }) {}",
+            "f(...a, b, ...[c]); new A(...b); x = [...a, , ...(b, c)];",
+            "function f(a, ...[b, c]) {} x = (...a) => a; y = (a = 1, ...{ length }) => a;",
+            "[a, ...b.c] = d; [...[e]] = f;",
+            "x = `a${b}c\\n${`d${e}`}` + tag`\\unicode`; new (f())`g`; (a + b)`c`;",
+            "x = /[/]\\//gi.test(a / b); /=a/; y /= /b/;",
+            "x = a?.b.c?.[d](e)?.(f) ?? (g || h); (a?.b).c; new (a?.b)(); a ??= (b && c) ?? d;",
+            "x = (-a) ** b ** (c ** d) * (e ** f) ** g; x **= 2; a &&= b || c; a ||= (b, c);",
+            "x = { a, [b + c]: d, ...e, ...(f, g), get: 1, async() {}, [h]: (i, j) };",
+            "x = { a() {}, get b() { return 1 }, set [c](v) {}, async d() {}, *e() {}, async *f() {} };",
+            "({ a = 1, b: { c } = d, [e]: f, ...g } = h); i = ({ a = 1, ...b }) => a;",
+            "class A { [a] = 1; static [b]; get [c]() {} }",
+            "a: b: for (;;) { c: { break c } while (d) continue a; break b }",
+            "if (a) b: if (c) break b; else d(); e: f();",
+            "async function f() { await a; x = await (b, c); y = async () => await f(); } g(async function () {});",
+            "function* g() { yield; yield* a; x = yield (b, c); f(yield d, yield); x = a ? yield : yield b; }",
+            "x = async a => a; y = async (a, ...b) => { await a }; z = function* () {};",
+            "class A { async a() {} *b() {} static async *[c]() { for await (const d of e) yield d; } }",
+            "export default async function () {} export async function h() {}",
+        ] {
+            assert_round_trip(source);
+        }
+    }
+
+    fn expression_source() -> impl Strategy<Value = String> {
+        let leaf = prop_oneof![
+            "[a-e]",
+            (0u32..1000).prop_map(|n| n.to_string()),
+            Just("1.5e3".to_string()),
+            Just("'s'".to_string()),
+            Just("\"d\"".to_string()),
+            Just("true".to_string()),
+            Just("null".to_string()),
+        ];
+        let binary = prop::sample::select(vec![
+            "==",
+            "!=",
+            "===",
+            "!==",
+            "<",
+            "<=",
+            ">",
+            ">=",
+            "<<",
+            ">>",
+            ">>>",
+            "+",
+            "-",
+            "*",
+            "/",
+            "%",
+            "|",
+            "^",
+            "&",
+            "in",
+            "instanceof",
+            "&&",
+            "||",
+        ]);
+        let unary = prop::sample::select(vec!["-", "+", "!", "~", "typeof", "void"]);
+        let update = prop::sample::select(vec!["++", "--"]);
+        leaf.prop_recursive(4, 48, 4, move |inner| {
+            prop_oneof![
+                (inner.clone(), binary.clone(), inner.clone())
+                    .prop_map(|(l, op, r)| format!("{l} {op} {r}")),
+                (unary.clone(), inner.clone()).prop_map(|(op, e)| format!("{op} {e}")),
+                // modules are strict, where only properties can be deleted
+                (inner.clone(), "[a-e]").prop_map(|(e, p)| format!("delete ({e}).{p}")),
+                ("[a-e]", prop::bool::ANY, update.clone()).prop_map(|(id, prefix, op)| {
+                    if prefix {
+                        format!("{op}{id}.p")
+                    } else {
+                        format!("{id}[0]{op}")
+                    }
+                }),
+                inner.clone().prop_map(|e| format!("({e})")),
+                (inner.clone(), "[a-e]").prop_map(|(e, p)| format!("({e}).{p}")),
+                (inner.clone(), inner.clone()).prop_map(|(e, p)| format!("({e})[{p}]")),
+                (inner.clone(), prop::collection::vec(inner.clone(), 0..3))
+                    .prop_map(|(e, args)| format!("({e})({})", args.join(", "))),
+                (inner.clone(), prop::collection::vec(inner.clone(), 0..3))
+                    .prop_map(|(e, args)| format!("new ({e})({})", args.join(", "))),
+                (inner.clone(), "[a-e]").prop_map(|(e, p)| format!("(new ({e})).{p}")),
+                (inner.clone(), "[a-e]").prop_map(|(e, p)| format!("({e})?.{p}")),
+                (inner.clone(), inner.clone()).prop_map(|(e, p)| format!("({e})?.[{p}]")),
+                (inner.clone(), prop::collection::vec(inner.clone(), 0..3))
+                    .prop_map(|(e, args)| format!("({e})?.({})", args.join(", "))),
+                (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("(({a}) ?? ({b}))")),
+                (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("(({a}) ** ({b}))")),
+                prop::collection::vec(inner.clone(), 0..3)
+                    .prop_map(|items| format!("[{}]", items.join(", "))),
+                (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("{{ k: {a}, 'l': {b} }}")),
+                ("[a-e]", inner.clone(), inner.clone())
+                    .prop_map(|(id, a, b)| format!("{{ {id}, [{a}]: {b}, ...{a} }}")),
+                (inner.clone(), inner.clone(), inner.clone())
+                    .prop_map(|(a, b, c)| format!("{a} ? {b} : {c}")),
+                ("[a-e]", inner.clone()).prop_map(|(id, e)| format!("({id} = {e})")),
+                (inner.clone(), inner).prop_map(|(a, b)| format!("({a}, {b})")),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_print_parse_round_trip(expr in expression_source()) {
+            let source = format!("x = {expr};");
+            let printed = print_default(&source);
+            prop_assert_eq!(normalized_ast(&source), normalized_ast(&printed));
+            prop_assert_eq!(print_default(&printed), printed);
+        }
+    }
+}
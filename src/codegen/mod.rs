@@ -0,0 +1,4 @@
+//! Code generators that lower a parsed [`Program`](crate::ast::Program) back to source text.
+
+pub mod js;
+pub mod python;
@@ -0,0 +1,3035 @@
+//! Python code generator
+//!
+//! Lowers a [`Program`] to Python 3 source. Constructs without a direct Python counterpart are
+//! rewritten: `for` and `do ... while` loops become `while` loops, `switch` becomes an `if`
+//! chain inside a single-iteration loop, and assignments in expression position use `:=`.
+//! Destructuring stores each property or element in a variable of its own. Operators that
+//! behave differently in Python, like `+` on strings, `%` and `typeof`, call runtime helpers.
+
+use crate::ast::*;
+use crate::syntax::operator::*;
+
+const INDENT: &str = "    ";
+
+/// Runtime support for object literals, which allows both `obj.key` and `obj['key']` access.
+const JS_OBJECT: &str = "\
+class JSObject(dict):
+    def __getattr__(self, name):
+        try:
+            return self[name]
+        except KeyError:
+            raise AttributeError(name) from None
+
+    def __setattr__(self, name, value):
+        if isinstance(getattr(type(self), name, None), property):
+            object.__setattr__(self, name, value)
+        else:
+            self[name] = value
+
+    __delattr__ = dict.__delitem__
+";
+
+/// Runtime support for `throw` and `catch`, since Python can only raise exceptions.
+const JS_EXCEPTION: &str = "\
+class JSException(Exception):
+    def __init__(self, value):
+        super().__init__(value)
+        self.value = value
+
+
+def js_throw(value):
+    return value if isinstance(value, BaseException) else JSException(value)
+
+
+def js_catch(error):
+    return error.value if isinstance(error, JSException) else error
+";
+
+/// Runtime support for `for-in`, which iterates over the keys of an object or the indices of an array.
+const JS_KEYS: &str = "\
+def js_keys(value):
+    if isinstance(value, (list, str)):
+        return [str(i) for i in range(len(value))]
+    return list(value)
+";
+
+/// Runtime support for destructuring, where missing properties and elements are `None`.
+const JS_PATTERN: &str = "\
+def js_property(value, key):
+    return value.get(key) if isinstance(value, dict) else getattr(value, key, None)
+
+
+def js_element(value, index):
+    return value[index] if index < len(value) else None
+
+
+def js_rest(value, keys):
+    return JSObject({key: item for key, item in value.items() if key not in keys})
+";
+
+/// Runtime support for assignments to properties in expression position, which yield the
+/// stored value like in JavaScript.
+const JS_SET: &str = "\
+def js_setattr(obj, name, value):
+    setattr(obj, name, value)
+    return value
+
+
+def js_setitem(obj, key, value):
+    obj[key] = value
+    return value
+";
+
+/// Runtime support for `typeof`, with the JavaScript type names. `null` and `undefined` are both
+/// `None`, which is reported as `'undefined'`.
+const JS_TYPEOF: &str = "\
+def js_typeof(value):
+    if value is None:
+        return 'undefined'
+    if isinstance(value, bool):
+        return 'boolean'
+    if isinstance(value, (int, float)):
+        return 'number'
+    if isinstance(value, str):
+        return 'string'
+    return 'function' if callable(value) else 'object'
+
+
+def js_typeof_name(get):
+    try:
+        return js_typeof(get())
+    except NameError:
+        return 'undefined'
+";
+
+/// Runtime support for `+`, which concatenates strings once either operand is a string or an
+/// object, converting the other operand like `String(value)`.
+const JS_ADD: &str = "\
+def js_string(value):
+    if value is None:
+        return 'undefined'
+    if isinstance(value, bool):
+        return 'true' if value else 'false'
+    if isinstance(value, float):
+        if value != value:
+            return 'NaN'
+        if value in (float('inf'), float('-inf')):
+            return 'Infinity' if value > 0 else '-Infinity'
+        if value.is_integer():
+            return str(int(value))
+    if isinstance(value, list):
+        return ','.join('' if item is None else js_string(item) for item in value)
+    if isinstance(value, dict):
+        return '[object Object]'
+    return str(value)
+
+
+def js_add(left, right):
+    if isinstance(left, (str, list, dict)) or isinstance(right, (str, list, dict)):
+        return js_string(left) + js_string(right)
+    return left + right
+";
+
+/// Runtime support for `%`, whose result has the sign of the dividend in JavaScript.
+const JS_REMAINDER: &str = "\
+def js_remainder(left, right):
+    if right == 0:
+        return float('nan')
+    if isinstance(left, int) and isinstance(right, int):
+        remainder = abs(left) % abs(right)
+        return -remainder if left < 0 else remainder
+    return __import__('math').fmod(left, right)
+";
+
+/// The `re` module, for regular expression literals, renamed so that it cannot be shadowed.
+const JS_RE: &str = "import re as js_re\n";
+
+/// The parameters of a static block, which takes none
+static NO_PARAMETERS: FormalParameters = FormalParameters {
+    span: Span { start: 0, end: 0 },
+    params: Vec::new(),
+    rest: None,
+};
+
+/// Python keywords that are valid JavaScript identifiers, renamed with a trailing `_`.
+#[rustfmt::skip]
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "def", "del", "elif",
+    "except", "from", "global", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+];
+
+/// Python operator precedence, from loosest to tightest binding.
+///
+/// ## References
+/// - [6.17 Operator precedence](https://docs.python.org/3/reference/expressions.html#operator-precedence)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+enum PyPrecedence {
+    Lowest,
+    Lambda,
+    Conditional,
+    Or,
+    And,
+    Not,
+    Compare,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    Shift,
+    Add,
+    Multiply,
+    Unary,
+    Power,
+    Await,
+    Primary,
+}
+
+impl PyPrecedence {
+    /// The next tighter level, used for the right operand of left-associative operators.
+    fn tighter(self) -> Self {
+        match self {
+            Self::Lowest => Self::Lambda,
+            Self::Lambda => Self::Conditional,
+            Self::Conditional => Self::Or,
+            Self::Or => Self::And,
+            Self::And => Self::Not,
+            Self::Not => Self::Compare,
+            Self::Compare => Self::BitwiseOr,
+            Self::BitwiseOr => Self::BitwiseXor,
+            Self::BitwiseXor => Self::BitwiseAnd,
+            Self::BitwiseAnd => Self::Shift,
+            Self::Shift => Self::Add,
+            Self::Add => Self::Multiply,
+            Self::Multiply => Self::Unary,
+            Self::Unary => Self::Power,
+            Self::Power => Self::Await,
+            Self::Await | Self::Primary => Self::Primary,
+        }
+    }
+}
+
+/// Enclosing breakable statements, innermost last.
+enum Frame<'a> {
+    /// A loop lowered to `while`; `continue` must first run the loop's update clause.
+    Loop(Option<&'a Expression<'a>>),
+    /// A `do ... while` lowered to `while True`; `continue` must first check the test.
+    DoWhile(&'a Expression<'a>),
+    /// A `switch` lowered to a one-shot `while True`.
+    Switch,
+    /// A labeled statement other than a loop, lowered to a one-shot `while True` so that
+    /// `break` can leave it.
+    Labeled,
+}
+
+/// A `break` or `continue`, with its label
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Jump<'a> {
+    Break(Option<&'a str>),
+    Continue(Option<&'a str>),
+}
+
+/// The flag of a frame that jumps to an enclosing frame have to leave first. Python can only
+/// leave the innermost loop, so the jump is stored in the flag and taken once the loop is left.
+struct Escape<'a> {
+    /// The index of the frame in `frames`
+    depth: usize,
+    flag: String,
+    /// The jumps the flag may be set to
+    jumps: Vec<Jump<'a>>,
+}
+
+/// What a `def` is lowered from
+enum Def<'a> {
+    Function,
+    /// An arrow function with a concise body, whose expression is returned
+    ConciseArrow,
+    /// A class method, which takes the instance (or the class, if static) as `self`
+    Method {
+        /// Instance fields to initialize, for constructors
+        fields: Vec<&'a PropertyDefinition<'a>>,
+        /// Initialize the fields after the `super(...)` call instead of on entry
+        derived: bool,
+    },
+}
+
+/// A class member whose name is only known at runtime, added once the class is defined
+enum ComputedMember<'a> {
+    /// A method or accessor defined in the class body under a temporary name
+    Method {
+        temp: String,
+        kind: MethodDefinitionKind,
+    },
+    /// A static field
+    Field(Option<&'a Expression<'a>>),
+}
+
+#[derive(Default)]
+pub struct PythonCodegen<'a> {
+    source_text: &'a str,
+    code: String,
+    indent: usize,
+    frames: Vec<Frame<'a>>,
+    /// Labels of the enclosing statements, with the index in `frames` of the frame each labels
+    labels: Vec<(&'a str, usize)>,
+    /// Flags of the enclosing frames that jumps escape from, innermost last
+    escapes: Vec<Escape<'a>>,
+    /// Names declared by the module and every enclosing function, outermost first
+    scopes: Vec<Vec<&'a str>>,
+    /// Counter for generated helper variables
+    temp_count: usize,
+    /// Runtime support definitions the generated code depends on
+    prelude: Vec<&'static str>,
+}
+
+impl<'a> PythonCodegen<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(mut self, program: &'a Program<'a>) -> String {
+        self.source_text = program.source_text;
+        let mut declared = vec![];
+        collect_declarations(&program.body, &mut declared);
+        self.scopes.push(declared);
+        for stmt in &program.body {
+            self.statement(stmt);
+        }
+        if self.prelude.is_empty() {
+            return self.code;
+        }
+        let mut code = self.prelude.join("\n");
+        code.push('\n');
+        code.push_str(&self.code);
+        code
+    }
+}
+
+// helpers:
+impl<'a> PythonCodegen<'a> {
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.code.push_str(INDENT);
+        }
+        self.code.push_str(text);
+        self.code.push('\n');
+    }
+
+    /// Emit an indented block, falling back to `pass` if `body` emits nothing
+    fn suite(&mut self, body: impl FnOnce(&mut Self)) {
+        self.indent += 1;
+        let len = self.code.len();
+        body(self);
+        if self.code.len() == len {
+            self.line("pass");
+        }
+        self.indent -= 1;
+    }
+
+    fn require(&mut self, definition: &'static str) {
+        if !self.prelude.contains(&definition) {
+            self.prelude.push(definition);
+        }
+    }
+
+    fn temp(&mut self, prefix: &str) -> String {
+        let name = format!("_{}_{}", prefix, self.temp_count);
+        self.temp_count += 1;
+        name
+    }
+
+    /// A property name as a dict key
+    fn property_key(&mut self, key: &'a PropertyKey<'a>) -> String {
+        match key {
+            PropertyKey::IdentifierName(ident) => format!("'{}'", ident.name),
+            PropertyKey::StringLiteral(lit) => lit.value.to_string(),
+            PropertyKey::NumericLiteral(lit) => lit.raw.to_string(),
+            PropertyKey::Computed(expr) => self.expression_prec(expr, PyPrecedence::Lambda),
+        }
+    }
+}
+
+// statements:
+impl<'a> PythonCodegen<'a> {
+    fn statement(&mut self, stmt: &'a Statement<'a>) {
+        match stmt {
+            Statement::BlockStatement(block) => self.statements(&block.body),
+            Statement::IfStatement(stmt) => self.if_statement(stmt, "if"),
+            Statement::ExpressionStatement(stmt) => self.expression_statement(&stmt.expression),
+            Statement::EmptyStatement(_) => {}
+            Statement::ReturnStatement(stmt) => match &stmt.argument {
+                Some(argument) => {
+                    let argument = self.expression(argument);
+                    self.line(&format!("return {argument}"));
+                }
+                None => self.line("return"),
+            },
+            Statement::ForStatement(stmt) => self.for_statement(stmt),
+            Statement::ForInStatement(stmt) => {
+                self.require(JS_KEYS);
+                let right = self.expression(&stmt.right);
+                let iterable = format!("js_keys({right})");
+                self.for_in_of_statement("for", &stmt.left, &iterable, &stmt.body);
+            }
+            Statement::ForOfStatement(stmt) => {
+                let right = self.expression(&stmt.right);
+                let keyword = if stmt.r#await { "async for" } else { "for" };
+                self.for_in_of_statement(keyword, &stmt.left, &right, &stmt.body);
+            }
+            Statement::WhileStatement(stmt) => {
+                let test = self.expression(&stmt.test);
+                let header = format!("while {test}:");
+                self.loop_body(&header, Frame::Loop(None), &stmt.body, |_| {});
+            }
+            Statement::DoWhileStatement(stmt) => {
+                self.loop_body("while True:", Frame::DoWhile(&stmt.test), &stmt.body, |p| {
+                    p.do_while_test(&stmt.test)
+                });
+            }
+            Statement::BreakStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| label.name);
+                self.jump(Jump::Break(label), self.frames.len());
+            }
+            Statement::ContinueStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| label.name);
+                self.jump(Jump::Continue(label), self.frames.len());
+            }
+            Statement::LabeledStatement(stmt) => self.labeled_statement(stmt),
+            Statement::SwitchStatement(stmt) => self.switch_statement(stmt),
+            Statement::WithStatement(stmt) => {
+                // Python has no object environments, names are resolved lexically instead
+                self.expression_statement(&stmt.object);
+                self.statement(&stmt.body);
+            }
+            Statement::VariableDeclarationStatement(decl) => self.variable_declaration(decl),
+            Statement::FunctionDeclaration(func) => self.function(func),
+            Statement::ClassDeclaration(class) => {
+                let name = class.id.as_ref().map_or("_anonymous", |id| id.name);
+                self.class(class, &identifier_name(name));
+            }
+            Statement::ImportDeclaration(decl) => self.import_declaration(decl),
+            Statement::ExportNamedDeclaration(decl) => self.export_named_declaration(decl),
+            Statement::ExportDefaultDeclaration(decl) => self.export_default_declaration(decl),
+            Statement::ExportAllDeclaration(decl) => {
+                let module = module_name(&decl.source);
+                match &decl.exported {
+                    Some(exported) => {
+                        let exported = module_export_name(exported);
+                        self.line(&format!("import {module} as {exported}"));
+                    }
+                    None => self.line(&format!("from {module} import *")),
+                }
+            }
+            Statement::ThrowStatement(stmt) => {
+                self.require(JS_EXCEPTION);
+                let argument = self.expression(&stmt.argument);
+                self.line(&format!("raise js_throw({argument})"));
+            }
+            Statement::TryStatement(stmt) => self.try_statement(stmt),
+            Statement::Error(stmt) => {
+                let text = self.source_text.get(stmt.span.start..stmt.span.end);
+                let text = python_string(text.unwrap_or_default().trim_end());
+                self.line(&format!("raise SyntaxError({text})"));
+            }
+        }
+    }
+
+    fn if_statement(&mut self, stmt: &'a IfStatement<'a>, keyword: &str) {
+        let test = self.expression(&stmt.test);
+        self.line(&format!("{keyword} {test}:"));
+        self.suite(|p| p.statement(&stmt.consequent));
+        match &stmt.alternate {
+            Some(Statement::IfStatement(alternate)) => self.if_statement(alternate, "elif"),
+            Some(alternate) => {
+                self.line("else:");
+                self.suite(|p| p.statement(alternate));
+            }
+            None => {}
+        }
+    }
+
+    fn try_statement(&mut self, stmt: &'a TryStatement<'a>) {
+        self.line("try:");
+        self.suite(|p| p.statements(&stmt.block.body));
+        if let Some(handler) = &stmt.handler {
+            match &handler.param {
+                Some(param) => {
+                    self.require(JS_EXCEPTION);
+                    let name = match param {
+                        BindingPattern::BindingIdentifier(ident) => identifier_name(ident.name),
+                        _ => self.temp("error"),
+                    };
+                    self.line(&format!("except Exception as {name}:"));
+                    self.indent += 1;
+                    match param {
+                        BindingPattern::BindingIdentifier(_) => {
+                            self.line(&format!("{name} = js_catch({name})"))
+                        }
+                        param => self.binding_pattern(param, format!("js_catch({name})")),
+                    }
+                    self.indent -= 1;
+                }
+                None => self.line("except Exception:"),
+            }
+            self.suite(|p| p.statements(&handler.body.body));
+        }
+        if let Some(finalizer) = &stmt.finalizer {
+            self.line("finally:");
+            self.suite(|p| p.statements(&finalizer.body));
+        }
+    }
+
+    fn import_declaration(&mut self, decl: &'a ImportDeclaration<'a>) {
+        let module = module_name(&decl.source);
+        if decl.specifiers.is_empty() {
+            self.line(&format!("import {module}"));
+        }
+        let mut names = vec![];
+        for specifier in &decl.specifiers {
+            match specifier {
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                    let local = identifier_name(specifier.local.name);
+                    names.push(format!("default as {local}"));
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                    let local = identifier_name(specifier.local.name);
+                    self.line(&format!("import {module} as {local}"));
+                }
+                ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                    let imported = module_export_name(&specifier.imported);
+                    let local = identifier_name(specifier.local.name);
+                    match imported == local {
+                        true => names.push(local),
+                        false => names.push(format!("{imported} as {local}")),
+                    }
+                }
+            }
+        }
+        if !names.is_empty() {
+            self.line(&format!("from {module} import {}", names.join(", ")));
+        }
+    }
+
+    /// Module attributes are public in Python, so only renamed and re-exported names need code
+    fn export_named_declaration(&mut self, decl: &'a ExportNamedDeclaration<'a>) {
+        if let Some(declaration) = &decl.declaration {
+            self.statement(declaration);
+        }
+        for specifier in &decl.specifiers {
+            let local = module_export_name(&specifier.local);
+            let exported = module_export_name(&specifier.exported);
+            match &decl.source {
+                Some(source) => {
+                    let module = module_name(source);
+                    self.line(&format!("from {module} import {local} as {exported}"));
+                }
+                None if local != exported => self.line(&format!("{exported} = {local}")),
+                None => {}
+            }
+        }
+    }
+
+    /// The default export is the module attribute `default`
+    fn export_default_declaration(&mut self, decl: &'a ExportDefaultDeclaration<'a>) {
+        let name = match &decl.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                let name = func.id.as_ref().map_or("default", |id| id.name);
+                let name = identifier_name(name);
+                let body = function_statements(func);
+                self.function_def(&name, &func.params, body, Def::Function, func.is_async);
+                name
+            }
+            ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                let name = class.id.as_ref().map_or("default", |id| id.name);
+                let name = identifier_name(name);
+                self.class(class, &name);
+                name
+            }
+            ExportDefaultDeclarationKind::Expression(expr) => self.expression(expr),
+        };
+        if name != "default" {
+            self.line(&format!("default = {name}"));
+        }
+    }
+
+    fn statements(&mut self, stmts: &'a [Statement<'a>]) {
+        for stmt in stmts {
+            self.statement(stmt);
+        }
+    }
+
+    fn for_statement(&mut self, stmt: &'a ForStatement<'a>) {
+        match &stmt.init {
+            Some(ForStatementInit::VariableDeclaration(decl)) => self.variable_declaration(decl),
+            Some(ForStatementInit::Expression(expr)) => self.expression_statement(expr),
+            None => {}
+        }
+        let test = match &stmt.test {
+            Some(test) => self.expression(test),
+            None => "True".to_string(),
+        };
+        let header = format!("while {test}:");
+        self.loop_body(
+            &header,
+            Frame::Loop(stmt.update.as_ref()),
+            &stmt.body,
+            |p| {
+                if let Some(update) = &stmt.update {
+                    p.expression_statement(update);
+                }
+            },
+        );
+    }
+
+    /// A Python `for` loop, which destructures each item at the start of the body if needed
+    fn for_in_of_statement(
+        &mut self,
+        keyword: &str,
+        left: &'a ForStatementLeft<'a>,
+        iterable: &str,
+        body: &'a Statement<'a>,
+    ) {
+        let item = match left {
+            ForStatementLeft::VariableDeclaration(decl) => match &decl.declarations[0].id {
+                BindingPattern::BindingIdentifier(ident) => identifier_name(ident.name),
+                _ => self.temp("item"),
+            },
+            ForStatementLeft::AssignmentTarget(target) if !is_pattern(target) => {
+                self.assignment_target(target)
+            }
+            ForStatementLeft::AssignmentTarget(_) => self.temp("item"),
+        };
+        self.push_frame(Frame::Loop(None), [body]);
+        self.line(&format!("{keyword} {item} in {iterable}:"));
+        self.suite(|p| {
+            match left {
+                ForStatementLeft::VariableDeclaration(decl) => {
+                    p.binding_pattern(&decl.declarations[0].id, item)
+                }
+                ForStatementLeft::AssignmentTarget(target) if is_pattern(target) => {
+                    p.assignment_target_pattern(target, item)
+                }
+                ForStatementLeft::AssignmentTarget(_) => {}
+            }
+            p.statement(body);
+        });
+        self.pop_frame();
+    }
+
+    /// A loop with `header`, running `tail` after each iteration of the body
+    fn loop_body(
+        &mut self,
+        header: &str,
+        frame: Frame<'a>,
+        body: &'a Statement<'a>,
+        tail: impl FnOnce(&mut Self),
+    ) {
+        self.push_frame(frame, [body]);
+        self.line(header);
+        self.suite(|p| {
+            p.statement(body);
+            tail(p);
+        });
+        self.pop_frame();
+    }
+
+    /// Enter the loop lowered from the statement with body `stmts`, starting with an escape
+    /// flag if jumps leave it for an enclosing frame
+    fn push_frame(&mut self, frame: Frame<'a>, stmts: impl IntoIterator<Item = &'a Statement<'a>>) {
+        let depth = self.frames.len();
+        let mut labels = self
+            .labels
+            .iter()
+            .filter(|(_, index)| *index == depth)
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        let (breaks, continues) = match frame {
+            Frame::Loop(_) | Frame::DoWhile(_) => (true, true),
+            Frame::Switch => (true, false),
+            Frame::Labeled => (false, false),
+        };
+        let mut jumps = vec![];
+        for stmt in stmts {
+            collect_escaping_jumps(stmt, &mut labels, breaks, continues, &mut jumps);
+        }
+        if !jumps.is_empty() {
+            let flag = self.temp("jump");
+            self.line(&format!("{flag} = None"));
+            self.escapes.push(Escape { depth, flag, jumps });
+        }
+        self.frames.push(frame);
+    }
+
+    /// Leave the innermost loop, then take the jumps that escaped from it
+    fn pop_frame(&mut self) {
+        self.frames.pop();
+        let depth = self.frames.len();
+        let Some(escape) = self.escapes.pop_if(|escape| escape.depth == depth) else {
+            return;
+        };
+        for jump in escape.jumps {
+            self.line(&format!("if {} == '{}':", escape.flag, jump_value(jump)));
+            self.suite(|p| p.jump(jump, depth));
+        }
+    }
+
+    /// `label: body`, where a body other than a loop or `switch` only needs a frame if it is left
+    /// with `break label`
+    fn labeled_statement(&mut self, stmt: &'a LabeledStatement<'a>) {
+        let mut labels = vec![stmt.label.name];
+        let mut body = &stmt.body;
+        while let Statement::LabeledStatement(stmt) = body {
+            labels.push(stmt.label.name);
+            body = &stmt.body;
+        }
+        let is_breakable = matches!(
+            body,
+            Statement::ForStatement(_)
+                | Statement::ForInStatement(_)
+                | Statement::ForOfStatement(_)
+                | Statement::WhileStatement(_)
+                | Statement::DoWhileStatement(_)
+                | Statement::SwitchStatement(_)
+        );
+        let mut jumps = vec![];
+        collect_escaping_jumps(body, &mut vec![], true, true, &mut jumps);
+        let is_target = jumps
+            .iter()
+            .any(|jump| matches!(jump, Jump::Break(Some(label)) if labels.contains(label)));
+        if !is_breakable && !is_target {
+            self.statement(body);
+            return;
+        }
+
+        let len = self.labels.len();
+        let depth = self.frames.len();
+        self.labels
+            .extend(labels.into_iter().map(|name| (name, depth)));
+        if is_breakable {
+            self.statement(body);
+        } else {
+            self.loop_body("while True:", Frame::Labeled, body, |p| p.line("break"));
+        }
+        self.labels.truncate(len);
+    }
+
+    fn do_while_test(&mut self, test: &'a Expression<'a>) {
+        let test = self.expression_prec(test, PyPrecedence::Not);
+        self.line(&format!("if not {test}:"));
+        self.suite(|p| p.line("break"));
+    }
+
+    /// Emit a `break` or `continue` from within `self.frames[..depth]`
+    fn jump(&mut self, jump: Jump<'a>, depth: usize) {
+        let frames = &self.frames[..depth];
+        let target = match jump {
+            Jump::Break(None) => frames
+                .iter()
+                .rposition(|frame| !matches!(frame, Frame::Labeled)),
+            Jump::Continue(None) => frames
+                .iter()
+                .rposition(|frame| matches!(frame, Frame::Loop(_) | Frame::DoWhile(_))),
+            Jump::Break(Some(label)) | Jump::Continue(Some(label)) => self
+                .labels
+                .iter()
+                .rfind(|(name, _)| *name == label)
+                .map(|(_, index)| *index),
+        };
+        // Only a `break` or `continue` outside of any loop has no target
+        let Some(target) = target else {
+            if let Jump::Break(_) = jump {
+                self.line("break");
+            }
+            return;
+        };
+
+        let index = depth - 1;
+        if target < index {
+            let escape = self
+                .escapes
+                .iter()
+                .rfind(|escape| escape.depth == index)
+                .expect("jumps out of a frame are collected when it is entered");
+            self.line(&format!("{} = '{}'", escape.flag, jump_value(jump)));
+            self.line("break");
+            return;
+        }
+        match (jump, &self.frames[index]) {
+            (Jump::Break(_), _) => self.line("break"),
+            (Jump::Continue(_), Frame::Loop(update)) => {
+                if let Some(update) = *update {
+                    self.expression_statement(update);
+                }
+                self.line("continue");
+            }
+            (Jump::Continue(_), Frame::DoWhile(test)) => {
+                self.do_while_test(test);
+                self.line("continue");
+            }
+            (Jump::Continue(_), Frame::Switch | Frame::Labeled) => {
+                unreachable!("`continue` only targets loops")
+            }
+        }
+    }
+
+    fn switch_statement(&mut self, stmt: &'a SwitchStatement<'a>) {
+        let switch = self.temp("switch");
+        let fallthrough = self.temp("fallthrough");
+        let discriminant = self.expression(&stmt.discriminant);
+        self.line(&format!("{switch} = {discriminant}"));
+        self.line(&format!("{fallthrough} = False"));
+
+        let mut tests = vec![];
+        for case in &stmt.cases {
+            if let Some(test) = &case.test {
+                let test = self.expression_prec(test, PyPrecedence::BitwiseOr);
+                tests.push(format!("{switch} == {test}"));
+            }
+        }
+
+        let default = match tests.is_empty() {
+            true => "True".to_string(),
+            false => format!("not ({})", tests.join(" or ")),
+        };
+
+        let stmts = stmt.cases.iter().flat_map(|case| &case.consequent);
+        self.push_frame(Frame::Switch, stmts);
+        self.line("while True:");
+        self.suite(|p| {
+            let mut tests = tests.iter();
+            for case in &stmt.cases {
+                let matched = match &case.test {
+                    Some(_) => tests.next().unwrap_or(&default),
+                    None => &default,
+                };
+                p.line(&format!("if {fallthrough} or {matched}:"));
+                p.suite(|p| {
+                    p.line(&format!("{fallthrough} = True"));
+                    for stmt in &case.consequent {
+                        p.statement(stmt);
+                    }
+                });
+            }
+            p.line("break");
+        });
+        self.pop_frame();
+    }
+
+    fn variable_declaration(&mut self, decl: &'a VariableDeclaration<'a>) {
+        for declarator in &decl.declarations {
+            let BindingPattern::BindingIdentifier(id) = &declarator.id else {
+                let init = match &declarator.init {
+                    Some(init) => self.expression(init),
+                    None => "None".to_string(),
+                };
+                self.binding_pattern(&declarator.id, init);
+                continue;
+            };
+            let id = identifier_name(id.name);
+            let init = match &declarator.init {
+                Some(Expression::AssignmentExpression(init))
+                    if init.operator == AssignmentOperator::Assign && !is_pattern(&init.left) =>
+                {
+                    self.assignment_statement(init)
+                }
+                Some(init) => self.expression(init),
+                None => "None".to_string(),
+            };
+            self.line(&format!("{id} = {init}"));
+        }
+    }
+
+    fn function(&mut self, func: &'a Function<'a>) {
+        let name = func.id.as_ref().map_or("_anonymous", |id| id.name);
+        self.function_def(
+            &identifier_name(name),
+            &func.params,
+            function_statements(func),
+            Def::Function,
+            func.is_async,
+        );
+    }
+
+    fn function_def(
+        &mut self,
+        name: &str,
+        params: &'a FormalParameters<'a>,
+        body: &'a [Statement<'a>],
+        def: Def<'a>,
+        is_async: bool,
+    ) {
+        // Patterns are passed as a single argument, and destructured on entry.
+        // Defaults apply to `None`, so every later parameter gets a default too.
+        let (mut arguments, mut locals) = (vec![], vec![]);
+        let mut has_default = false;
+        for param in &params.params {
+            let name = match param {
+                BindingPattern::BindingIdentifier(ident) => identifier_name(ident.name),
+                BindingPattern::AssignmentPattern(pattern) => match &pattern.left {
+                    BindingPattern::BindingIdentifier(ident) => identifier_name(ident.name),
+                    _ => self.temp("arg"),
+                },
+                _ => self.temp("arg"),
+            };
+            has_default |= matches!(param, BindingPattern::AssignmentPattern(_));
+            arguments.push(match has_default {
+                true => format!("{name}=None"),
+                false => name.clone(),
+            });
+            locals.push(name);
+        }
+        // The rest arrives as a tuple, and is destructured on entry like the other patterns
+        let rest = params.rest.as_ref().map(|rest| match &rest.argument {
+            BindingPattern::BindingIdentifier(ident) => identifier_name(ident.name),
+            _ => self.temp("arg"),
+        });
+        if let Some(rest) = &rest {
+            arguments.push(format!("*{rest}"));
+        }
+        let receiver = matches!(def, Def::Method { .. }).then(|| "self".to_string());
+        let params_code = receiver
+            .into_iter()
+            .chain(arguments)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let keyword = if is_async { "async def" } else { "def" };
+        self.line(&format!("{keyword} {name}({params_code}):"));
+
+        let mut declared = vec![];
+        for param in &params.params {
+            collect_binding_names(param, &mut declared);
+        }
+        if let Some(rest) = &params.rest {
+            collect_binding_names(&rest.argument, &mut declared);
+        }
+        let mut assigned = vec![];
+        collect_declarations(body, &mut declared);
+        for stmt in body {
+            collect_assignments(stmt, &mut assigned);
+        }
+
+        let (mut nonlocals, mut globals) = (vec![], vec![]);
+        for name in assigned {
+            if declared.contains(&name) {
+                continue;
+            }
+            let enclosing = self.scopes[1..].iter().any(|scope| scope.contains(&name));
+            let name = identifier_name(name);
+            let list = if enclosing {
+                &mut nonlocals
+            } else {
+                &mut globals
+            };
+            if !list.contains(&name) {
+                list.push(name);
+            }
+        }
+
+        let frames = std::mem::take(&mut self.frames);
+        let labels = std::mem::take(&mut self.labels);
+        let escapes = std::mem::take(&mut self.escapes);
+        self.scopes.push(declared);
+        self.suite(|p| {
+            if !nonlocals.is_empty() {
+                p.line(&format!("nonlocal {}", nonlocals.join(", ")));
+            }
+            if !globals.is_empty() {
+                p.line(&format!("global {}", globals.join(", ")));
+            }
+            for (param, local) in params.params.iter().zip(locals) {
+                p.binding_pattern(param, local);
+            }
+            if let (Some(rest), Some(local)) = (&params.rest, rest) {
+                p.line(&format!("{local} = list({local})"));
+                p.binding_pattern(&rest.argument, local);
+            }
+            let concise = matches!(def, Def::ConciseArrow);
+            let (mut fields, derived) = match def {
+                Def::Method { fields, derived } => (fields, derived),
+                Def::Function | Def::ConciseArrow => (vec![], false),
+            };
+            if !derived {
+                p.class_fields(&std::mem::take(&mut fields));
+            }
+            for stmt in body {
+                match stmt {
+                    Statement::ExpressionStatement(stmt) if concise => {
+                        let value = p.expression(&stmt.expression);
+                        p.line(&format!("return {value}"));
+                    }
+                    stmt => {
+                        p.statement(stmt);
+                        if is_super_call(stmt) {
+                            p.class_fields(&std::mem::take(&mut fields));
+                        }
+                    }
+                }
+            }
+        });
+        self.scopes.pop();
+        self.frames = frames;
+        self.labels = labels;
+        self.escapes = escapes;
+    }
+
+    fn class(&mut self, class: &'a Class<'a>, name: &str) {
+        let bases = match &class.super_class {
+            Some(super_class) => format!("({})", self.expression(super_class)),
+            None => String::new(),
+        };
+        self.line(&format!("class {name}{bases}:"));
+
+        let derived = class.super_class.is_some();
+        let fields = class
+            .body
+            .body
+            .iter()
+            .filter_map(|element| match element {
+                ClassElement::PropertyDefinition(prop) if !prop.r#static => Some(&**prop),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let has_constructor = class.body.body.iter().any(|element| {
+            matches!(element, ClassElement::MethodDefinition(method)
+                if method.kind == MethodDefinitionKind::Constructor)
+        });
+        let mut static_blocks = vec![];
+        let mut computed = vec![];
+        self.suite(|p| {
+            if !has_constructor && !fields.is_empty() {
+                p.line("def __init__(self, *args, **kwargs):");
+                p.suite(|p| {
+                    if derived {
+                        p.line("super().__init__(*args, **kwargs)");
+                    }
+                    p.class_fields(&fields);
+                });
+            }
+            for element in &class.body.body {
+                match element {
+                    ClassElement::MethodDefinition(method) => {
+                        let fields = match method.kind {
+                            MethodDefinitionKind::Constructor => fields.clone(),
+                            _ => vec![],
+                        };
+                        let def = Def::Method { fields, derived };
+                        p.method(
+                            &method.key,
+                            method.kind,
+                            method.r#static,
+                            &method.value,
+                            def,
+                            &mut computed,
+                        );
+                    }
+                    ClassElement::PropertyDefinition(prop) if prop.r#static => {
+                        if let PropertyKey::Computed(key) = &prop.key {
+                            computed.push((key, ComputedMember::Field(prop.value.as_ref())));
+                            continue;
+                        }
+                        let value = match &prop.value {
+                            Some(value) => p.expression(value),
+                            None => "None".to_string(),
+                        };
+                        p.line(&format!("{} = {value}", class_member_name(&prop.key)));
+                    }
+                    ClassElement::PropertyDefinition(_) => {}
+                    ClassElement::StaticBlock(block) => static_blocks.push(block),
+                }
+            }
+        });
+        self.computed_members(name, computed);
+
+        // run with the class as `self` once it is defined
+        for block in static_blocks {
+            let temp = self.temp("static");
+            let def = Def::Method {
+                fields: vec![],
+                derived: false,
+            };
+            self.function_def(&temp, &NO_PARAMETERS, &block.body, def, false);
+            self.line(&format!("{temp}({name})"));
+        }
+    }
+
+    /// Initialize instance fields on `self`
+    fn class_fields(&mut self, fields: &[&'a PropertyDefinition<'a>]) {
+        for prop in fields {
+            let value = match &prop.value {
+                Some(value) => self.expression(value),
+                None => "None".to_string(),
+            };
+            if let PropertyKey::Computed(key) = &prop.key {
+                let key = self.expression_prec(key, PyPrecedence::Lambda);
+                self.line(&format!("setattr(self, {key}, {value})"));
+                continue;
+            }
+            let name = class_member_name(&prop.key);
+            self.line(&format!("self.{name} = {value}"));
+        }
+    }
+
+    /// A method or accessor of a class body; one with a computed name is defined under a
+    /// temporary name and added to `computed`
+    fn method(
+        &mut self,
+        key: &'a PropertyKey<'a>,
+        kind: MethodDefinitionKind,
+        r#static: bool,
+        func: &'a Function<'a>,
+        def: Def<'a>,
+        computed: &mut Vec<(&'a Expression<'a>, ComputedMember<'a>)>,
+    ) {
+        let name = match (kind, key) {
+            (MethodDefinitionKind::Constructor, _) => "__init__".to_string(),
+            (kind, PropertyKey::Computed(key)) => {
+                let temp = self.temp("member");
+                let member = ComputedMember::Method {
+                    temp: temp.clone(),
+                    kind,
+                };
+                computed.push((key, member));
+                temp
+            }
+            (_, key) => class_member_name(key),
+        };
+        if r#static {
+            self.line("@classmethod");
+        }
+        if !matches!(key, PropertyKey::Computed(_)) {
+            match kind {
+                MethodDefinitionKind::Get => self.line("@property"),
+                MethodDefinitionKind::Set => self.line(&format!("@{name}.setter")),
+                MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => {}
+            }
+        }
+        self.function_def(
+            &name,
+            &func.params,
+            function_statements(func),
+            def,
+            func.is_async,
+        );
+    }
+
+    /// Add the members with computed names to the class `name`, in order
+    fn computed_members(
+        &mut self,
+        name: &str,
+        computed: Vec<(&'a Expression<'a>, ComputedMember<'a>)>,
+    ) {
+        for (key, member) in computed {
+            let key = self.expression_prec(key, PyPrecedence::Lambda);
+            let value = match member {
+                ComputedMember::Method { temp, kind } => {
+                    let method = format!("vars({name})['{temp}']");
+                    match kind {
+                        MethodDefinitionKind::Get => format!("property({method})"),
+                        MethodDefinitionKind::Set => format!("property(None, {method})"),
+                        MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => method,
+                    }
+                }
+                ComputedMember::Field(Some(value)) => {
+                    self.expression_prec(value, PyPrecedence::Lambda)
+                }
+                ComputedMember::Field(None) => "None".to_string(),
+            };
+            self.line(&format!("setattr({name}, {key}, {value})"));
+        }
+    }
+
+    /// `JSObject({...})`, or an instance of a `JSObject` subclass defined first when the object
+    /// has methods or accessors, which need the object as `self`
+    fn object_expression(&mut self, object: &'a ObjectExpression<'a>) -> String {
+        let mut entries = vec![];
+        let mut methods = vec![];
+        for property in &object.properties {
+            match property {
+                ObjectPropertyKind::ObjectProperty(property) => match &property.value {
+                    Expression::FunctionExpression(func) if property.kind != PropertyKind::Init => {
+                        methods.push((property, func))
+                    }
+                    value => {
+                        let key = self.property_key(&property.key);
+                        let value = self.expression_prec(value, PyPrecedence::Lambda);
+                        entries.push(format!("{key}: {value}"));
+                    }
+                },
+                ObjectPropertyKind::SpreadProperty(spread) => {
+                    let value = self.expression_prec(&spread.argument, PyPrecedence::BitwiseOr);
+                    entries.push(format!("**{value}"));
+                }
+            }
+        }
+        self.require(JS_OBJECT);
+        let entries = entries.join(", ");
+        if methods.is_empty() {
+            return format!("JSObject({{{entries}}})");
+        }
+
+        let name = self.temp("obj");
+        self.line(&format!("class {name}(JSObject):"));
+        let mut computed = vec![];
+        self.suite(|p| {
+            for (property, func) in methods {
+                let kind = match property.kind {
+                    PropertyKind::Get => MethodDefinitionKind::Get,
+                    PropertyKind::Set => MethodDefinitionKind::Set,
+                    PropertyKind::Init | PropertyKind::Method => MethodDefinitionKind::Method,
+                };
+                let def = Def::Method {
+                    fields: vec![],
+                    derived: false,
+                };
+                p.method(&property.key, kind, false, func, def, &mut computed);
+            }
+        });
+        self.computed_members(&name, computed);
+        format!("{name}({{{entries}}})")
+    }
+
+    /// Emit an expression evaluated for its side effects only
+    fn expression_statement(&mut self, expr: &'a Expression<'a>) {
+        match expr {
+            Expression::AssignmentExpression(assign) if is_pattern(&assign.left) => {
+                let value = self.expression(&assign.right);
+                self.assignment_target_pattern(&assign.left, value);
+            }
+            Expression::AssignmentExpression(assign) => {
+                let code = self.assignment_statement(assign);
+                self.line(&code);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.expression_statement(expr);
+                }
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.expression_statement(&paren.expression)
+            }
+            Expression::UpdateExpression(update) => {
+                let target = self.assignment_target(&update.argument);
+                let op = update_operator(update.operator);
+                self.line(&format!("{target} {op}= 1"));
+            }
+            Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::Delete => {
+                let argument = self.expression_prec(&unary.argument, PyPrecedence::Primary);
+                self.line(&format!("del {argument}"));
+            }
+            Expression::YieldExpression(expr) => {
+                let code = self.yield_expression(expr);
+                self.line(&code);
+            }
+            _ => {
+                let code = self.expression(expr);
+                self.line(&code);
+            }
+        }
+    }
+
+    fn assignment_statement(&mut self, assign: &'a AssignmentExpression<'a>) -> String {
+        // the object and key of a property have to be evaluated once, like with `op=`
+        let augmented = augmented_operator(assign.operator).is_some();
+        if !augmented && assign.operator != AssignmentOperator::Assign {
+            if let AssignmentTarget::StaticMemberExpression(_)
+            | AssignmentTarget::ComputedMemberExpression(_) = &assign.left
+            {
+                return self.assignment_expression(assign);
+            }
+        }
+        let target = self.assignment_target(&assign.left);
+        if assign.operator == AssignmentOperator::Assign {
+            let value = match &assign.right {
+                Expression::AssignmentExpression(right)
+                    if right.operator == AssignmentOperator::Assign && !is_pattern(&right.left) =>
+                {
+                    self.assignment_statement(right)
+                }
+                right => self.expression(right),
+            };
+            return format!("{target} = {value}");
+        }
+        match augmented_operator(assign.operator) {
+            Some(op) => {
+                let value = self.expression(&assign.right);
+                format!("{target} {op}= {value}")
+            }
+            None => {
+                let value = self.compound_value(assign.operator, &target, &assign.right);
+                format!("{target} = {value}")
+            }
+        }
+    }
+}
+
+// patterns:
+impl<'a> PythonCodegen<'a> {
+    /// Bind the names in `pattern` to the parts of `value`
+    fn binding_pattern(&mut self, pattern: &'a BindingPattern<'a>, value: String) {
+        match pattern {
+            BindingPattern::BindingIdentifier(ident) => {
+                let name = identifier_name(ident.name);
+                if name != value {
+                    self.line(&format!("{name} = {value}"));
+                }
+            }
+            BindingPattern::ObjectPattern(object) => {
+                self.require(JS_PATTERN);
+                let object_value = self.pattern_value(value);
+                let mut keys = vec![];
+                for property in &object.properties {
+                    let key = self.property_key(&property.key);
+                    let value = format!("js_property({object_value}, {key})");
+                    self.binding_pattern(&property.value, value);
+                    keys.push(key);
+                }
+                if let Some(rest) = &object.rest {
+                    self.require(JS_OBJECT);
+                    let value = format!("js_rest({object_value}, [{}])", keys.join(", "));
+                    self.binding_pattern(&rest.argument, value);
+                }
+            }
+            BindingPattern::ArrayPattern(array) => {
+                self.require(JS_PATTERN);
+                let array_value = self.pattern_value(value);
+                for (index, element) in array.elements.iter().enumerate() {
+                    if let Some(element) = element {
+                        self.binding_pattern(
+                            element,
+                            format!("js_element({array_value}, {index})"),
+                        );
+                    }
+                }
+                if let Some(rest) = &array.rest {
+                    let value = format!("{array_value}[{}:]", array.elements.len());
+                    self.binding_pattern(&rest.argument, value);
+                }
+            }
+            BindingPattern::AssignmentPattern(pattern) => match &pattern.left {
+                BindingPattern::BindingIdentifier(ident) => {
+                    let name = identifier_name(ident.name);
+                    if name != value {
+                        self.line(&format!("{name} = {value}"));
+                    }
+                    self.default_value(&name, &pattern.right);
+                }
+                left => {
+                    let temp = self.temp("ref");
+                    self.line(&format!("{temp} = {value}"));
+                    self.default_value(&temp, &pattern.right);
+                    self.binding_pattern(left, temp);
+                }
+            },
+        }
+    }
+
+    /// Store the parts of `value` into an array or object assignment target
+    fn assignment_target_pattern(&mut self, target: &'a AssignmentTarget<'a>, value: String) {
+        match target {
+            AssignmentTarget::ArrayAssignmentTarget(array) => {
+                self.require(JS_PATTERN);
+                let array_value = self.pattern_value(value);
+                for (index, element) in array.elements.iter().enumerate() {
+                    if let Some(element) = element {
+                        let value = format!("js_element({array_value}, {index})");
+                        self.assignment_target_maybe_default(element, value);
+                    }
+                }
+                if let Some(rest) = &array.rest {
+                    let value = format!("{array_value}[{}:]", array.elements.len());
+                    self.assignment_target_pattern(&rest.target, value);
+                }
+            }
+            AssignmentTarget::ObjectAssignmentTarget(object) => {
+                self.require(JS_PATTERN);
+                let object_value = self.pattern_value(value);
+                let mut keys = vec![];
+                for property in &object.properties {
+                    let key = self.property_key(&property.key);
+                    let value = format!("js_property({object_value}, {key})");
+                    self.assignment_target_maybe_default(&property.value, value);
+                    keys.push(key);
+                }
+                if let Some(rest) = &object.rest {
+                    self.require(JS_OBJECT);
+                    let value = format!("js_rest({object_value}, [{}])", keys.join(", "));
+                    self.assignment_target_pattern(&rest.target, value);
+                }
+            }
+            target => {
+                let target = self.assignment_target(target);
+                self.line(&format!("{target} = {value}"));
+            }
+        }
+    }
+
+    fn assignment_target_maybe_default(
+        &mut self,
+        target: &'a AssignmentTargetMaybeDefault<'a>,
+        value: String,
+    ) {
+        match target {
+            AssignmentTargetMaybeDefault::AssignmentTarget(target) => {
+                self.assignment_target_pattern(target, value)
+            }
+            AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => {
+                let temp = self.temp("ref");
+                self.line(&format!("{temp} = {value}"));
+                self.default_value(&temp, &target.init);
+                self.assignment_target_pattern(&target.binding, temp);
+            }
+        }
+    }
+
+    /// `value` as a name that can be read repeatedly, stored in a temporary if needed
+    fn pattern_value(&mut self, value: String) -> String {
+        if value.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return value;
+        }
+        let temp = self.temp("ref");
+        self.line(&format!("{temp} = {value}"));
+        temp
+    }
+
+    /// Replace a missing value, which is `None`, with the default
+    fn default_value(&mut self, name: &str, default: &'a Expression<'a>) {
+        self.line(&format!("if {name} is None:"));
+        self.suite(|p| {
+            let default = p.expression(default);
+            p.line(&format!("{name} = {default}"));
+        });
+    }
+}
+
+// expressions:
+impl<'a> PythonCodegen<'a> {
+    fn expression(&mut self, expr: &'a Expression<'a>) -> String {
+        self.expression_prec(expr, PyPrecedence::Lowest)
+    }
+
+    /// Render `expr`, parenthesized if it binds looser than `min`
+    fn expression_prec(&mut self, expr: &'a Expression<'a>, min: PyPrecedence) -> String {
+        let (code, precedence) = self.expression_inner(expr);
+        if precedence < min {
+            format!("({code})")
+        } else {
+            code
+        }
+    }
+
+    fn expression_inner(&mut self, expr: &'a Expression<'a>) -> (String, PyPrecedence) {
+        use PyPrecedence::*;
+        match expr {
+            Expression::BooleanLiteral(lit) => {
+                let value = if lit.value { "True" } else { "False" };
+                (value.into(), Primary)
+            }
+            Expression::NullLiteral(_) => ("None".into(), Primary),
+            Expression::NumericLiteral(lit) => (lit.raw.into(), Primary),
+            Expression::StringLiteral(lit) => (lit.value.into(), Primary),
+            Expression::TemplateLiteral(template) => (self.template_literal(template), Primary),
+            Expression::RegExpLiteral(lit) => {
+                self.require(JS_RE);
+                let pattern = python_string(&python_regex_pattern(lit.pattern));
+                // `g`, `y` and `d` change how the methods of a JavaScript RegExp behave
+                let flags = lit
+                    .flags
+                    .chars()
+                    .filter_map(|flag| match flag {
+                        'i' => Some("js_re.I"),
+                        'm' => Some("js_re.M"),
+                        's' => Some("js_re.S"),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let code = match flags.is_empty() {
+                    true => format!("js_re.compile({pattern})"),
+                    false => format!("js_re.compile({pattern}, {})", flags.join(" | ")),
+                };
+                (code, Primary)
+            }
+            Expression::TaggedTemplateExpression(expr) => {
+                // the tag is called with the cooked strings, then the substitutions
+                let tag = self.expression_prec(&expr.tag, Primary);
+                let strings = expr
+                    .quasi
+                    .quasis
+                    .iter()
+                    .map(|quasi| {
+                        quasi
+                            .value
+                            .cooked
+                            .as_deref()
+                            .map_or("None".into(), python_string)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let arguments = std::iter::once(format!("[{strings}]"))
+                    .chain(
+                        expr.quasi
+                            .expressions
+                            .iter()
+                            .map(|expr| self.expression_prec(expr, Lambda)),
+                    )
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (format!("{tag}({arguments})"), Primary)
+            }
+            Expression::Identifier(ident) => {
+                let code = match ident.name {
+                    "undefined" => "None".into(),
+                    "NaN" => "float('nan')".into(),
+                    "Infinity" => "float('inf')".into(),
+                    name => identifier_name(name),
+                };
+                (code, Primary)
+            }
+            Expression::SequenceExpression(seq) => {
+                let items = seq
+                    .expressions
+                    .iter()
+                    .map(|expr| self.expression_prec(expr, Lambda))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (format!("({items})[-1]"), Primary)
+            }
+            Expression::BinaryExpression(expr) => {
+                self.binary_expression(expr.operator, &expr.left, &expr.right)
+            }
+            Expression::UnaryExpression(expr) => self.unary_expression(expr),
+            Expression::UpdateExpression(expr) => self.update_expression(expr),
+            Expression::LogicalExpression(expr) => {
+                self.logical_expression(expr.operator, &expr.left, &expr.right)
+            }
+            Expression::ConditionalExpression(expr) => {
+                let consequent = self.expression_prec(&expr.consequent, Or);
+                let test = self.expression_prec(&expr.test, Or);
+                let alternate = self.expression_prec(&expr.alternate, Lambda);
+                (
+                    format!("{consequent} if {test} else {alternate}"),
+                    Conditional,
+                )
+            }
+            Expression::AssignmentExpression(expr) => (self.assignment_expression(expr), Primary),
+            Expression::ArrayExpression(array) => {
+                let elements = array
+                    .elements
+                    .iter()
+                    .map(|element| match element {
+                        ArrayExpressionElement::Elision(_) => "None".to_string(),
+                        ArrayExpressionElement::Expression(expr) => {
+                            self.expression_prec(expr, Lambda)
+                        }
+                        ArrayExpressionElement::SpreadElement(spread) => {
+                            self.spread_element(spread)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (format!("[{elements}]"), Primary)
+            }
+            Expression::ObjectExpression(object) => (self.object_expression(object), Primary),
+            Expression::StaticMemberExpression(member) => {
+                let object = self.expression_prec(&member.object, Primary);
+                (static_member(object, member.property.name), Primary)
+            }
+            Expression::ComputedMemberExpression(member) => {
+                let object = self.expression_prec(&member.object, Primary);
+                let property = self.expression(&member.expression);
+                (format!("{object}[{property}]"), Primary)
+            }
+            Expression::CallExpression(call) => {
+                let callee = self.callee(&call.callee);
+                let arguments = call
+                    .arguments
+                    .iter()
+                    .map(|argument| self.argument(argument))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (format!("{callee}({arguments})"), Primary)
+            }
+            Expression::ChainExpression(chain) => {
+                let mut guards = vec![];
+                let code = self.chain_link(&chain.expression, &mut guards);
+                (
+                    format!("None if {} else {code}", guards.join(" or ")),
+                    Conditional,
+                )
+            }
+            Expression::NewExpression(expr) => {
+                let callee = match &expr.callee {
+                    Expression::Identifier(ident) if ident.name == "Error" => "Exception".into(),
+                    callee => self.expression_prec(callee, Primary),
+                };
+                let arguments = expr
+                    .arguments
+                    .iter()
+                    .map(|argument| self.argument(argument))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (format!("{callee}({arguments})"), Primary)
+            }
+            Expression::ThisExpression(_) => ("self".into(), Primary),
+            Expression::FunctionExpression(func) => {
+                let name = match &func.id {
+                    Some(id) => identifier_name(id.name),
+                    None => self.temp("fn"),
+                };
+                self.function_def(
+                    &name,
+                    &func.params,
+                    function_statements(func),
+                    Def::Function,
+                    func.is_async,
+                );
+                (name, Primary)
+            }
+            Expression::ClassExpression(class) => {
+                let name = match &class.id {
+                    Some(id) => identifier_name(id.name),
+                    None => self.temp("cls"),
+                };
+                self.class(class, &name);
+                (name, Primary)
+            }
+            Expression::Super(_) => ("super()".into(), Primary),
+            Expression::ArrowFunctionExpression(arrow) => self.arrow_function(arrow),
+            Expression::AwaitExpression(expr) => {
+                let argument = self.expression_prec(&expr.argument, Primary);
+                (format!("await {argument}"), Await)
+            }
+            // parenthesized unless it is the whole statement
+            Expression::YieldExpression(expr) => {
+                (format!("({})", self.yield_expression(expr)), Primary)
+            }
+            Expression::MetaProperty(meta) if meta.meta.name == "import" => {
+                self.require(JS_OBJECT);
+                ("JSObject({'url': 'file://' + __file__})".into(), Primary)
+            }
+            // Only meaningful in constructors, where it is the class of the new instance
+            Expression::MetaProperty(_) => ("type(self)".into(), Primary),
+            Expression::ImportExpression(expr) => {
+                let source = match &expr.source {
+                    Expression::StringLiteral(lit) => python_string(&module_name(lit)),
+                    source => self.expression_prec(source, Lambda),
+                };
+                (
+                    format!("__import__('importlib').import_module({source})"),
+                    Primary,
+                )
+            }
+            Expression::ParenthesizedExpression(paren) => self.expression_inner(&paren.expression),
+        }
+    }
+
+    fn binary_expression(
+        &mut self,
+        operator: BinaryOperator,
+        left: &'a Expression<'a>,
+        right: &'a Expression<'a>,
+    ) -> (String, PyPrecedence) {
+        use PyPrecedence::*;
+        let (op, precedence) = match operator {
+            BinaryOperator::Instanceof => {
+                let left = self.expression_prec(left, Lambda);
+                let right = self.expression_prec(right, Lambda);
+                return (format!("isinstance({left}, {right})"), Primary);
+            }
+            BinaryOperator::ShiftRightZeroFill => {
+                let left = self.expression_prec(left, Multiply);
+                let right = self.expression_prec(right, Add);
+                return (format!("({left} % 0x100000000) >> {right}"), Shift);
+            }
+            BinaryOperator::Exponential => {
+                let left = self.expression_prec(left, Await);
+                let right = self.expression_prec(right, Unary);
+                return (format!("{left} ** {right}"), Power);
+            }
+            // Python's `+` only works like JavaScript's when both operands are numbers
+            BinaryOperator::Addition if !(is_number(left) && is_number(right)) => {
+                self.require(JS_ADD);
+                let left = self.expression_prec(left, Lambda);
+                let right = self.expression_prec(right, Lambda);
+                return (format!("js_add({left}, {right})"), Primary);
+            }
+            BinaryOperator::Remainder => {
+                self.require(JS_REMAINDER);
+                let left = self.expression_prec(left, Lambda);
+                let right = self.expression_prec(right, Lambda);
+                return (format!("js_remainder({left}, {right})"), Primary);
+            }
+            BinaryOperator::Equality | BinaryOperator::StrictEquality => ("==", Compare),
+            BinaryOperator::Inequality | BinaryOperator::StrictInequality => ("!=", Compare),
+            BinaryOperator::LessThan => ("<", Compare),
+            BinaryOperator::LessEqualThan => ("<=", Compare),
+            BinaryOperator::GreaterThan => (">", Compare),
+            BinaryOperator::GreaterEqualThan => (">=", Compare),
+            BinaryOperator::In => ("in", Compare),
+            BinaryOperator::ShiftLeft => ("<<", Shift),
+            BinaryOperator::ShiftRight => (">>", Shift),
+            BinaryOperator::Addition => ("+", Add),
+            BinaryOperator::Subtraction => ("-", Add),
+            BinaryOperator::Multiplication => ("*", Multiply),
+            BinaryOperator::Division => ("/", Multiply),
+            BinaryOperator::BitwiseOR => ("|", BitwiseOr),
+            BinaryOperator::BitwiseXOR => ("^", BitwiseXor),
+            BinaryOperator::BitwiseAnd => ("&", BitwiseAnd),
+        };
+        // Python chains comparisons (`a < b < c`), so nested comparisons need parentheses
+        let left_min = if precedence == Compare {
+            precedence.tighter()
+        } else {
+            precedence
+        };
+        let left = self.expression_prec(left, left_min);
+        let right = self.expression_prec(right, precedence.tighter());
+        (format!("{left} {op} {right}"), precedence)
+    }
+
+    fn logical_expression(
+        &mut self,
+        operator: LogicalOperator,
+        left: &'a Expression<'a>,
+        right: &'a Expression<'a>,
+    ) -> (String, PyPrecedence) {
+        use PyPrecedence::*;
+        let (op, precedence) = match operator {
+            LogicalOperator::Or => ("or", Or),
+            LogicalOperator::And => ("and", And),
+            LogicalOperator::Coalesce => {
+                // The test runs first, so anything but a name is evaluated once into a temporary
+                let (value, test) = match left {
+                    Expression::Identifier(_) => {
+                        let value = self.expression_prec(left, Or);
+                        (value.clone(), value)
+                    }
+                    left => {
+                        let value = self.expression_prec(left, Lambda);
+                        let temp = self.temp("ref");
+                        (temp.clone(), format!("({temp} := {value})"))
+                    }
+                };
+                let right = self.expression_prec(right, Lambda);
+                return (
+                    format!("{value} if {test} is not None else {right}"),
+                    Conditional,
+                );
+            }
+        };
+        let left = self.expression_prec(left, precedence);
+        let right = self.expression_prec(right, precedence.tighter());
+        (format!("{left} {op} {right}"), precedence)
+    }
+
+    /// `yield*` delegates with `yield from`
+    fn yield_expression(&mut self, expr: &'a YieldExpression<'a>) -> String {
+        let keyword = if expr.delegate { "yield from" } else { "yield" };
+        match &expr.argument {
+            Some(argument) => {
+                let argument = self.expression_prec(argument, PyPrecedence::Lambda);
+                format!("{keyword} {argument}")
+            }
+            None => keyword.into(),
+        }
+    }
+
+    fn unary_expression(&mut self, expr: &'a UnaryExpression<'a>) -> (String, PyPrecedence) {
+        use PyPrecedence::*;
+        let (op, precedence) = match expr.operator {
+            UnaryOperator::LogicalNot => ("not ", Not),
+            UnaryOperator::UnaryNegation => ("-", Unary),
+            UnaryOperator::UnaryPlus => ("+", Unary),
+            UnaryOperator::BitwiseNot => ("~", Unary),
+            UnaryOperator::Typeof => {
+                self.require(JS_TYPEOF);
+                // `typeof` of a name that was never declared does not throw
+                if let Expression::Identifier(ident) = &expr.argument {
+                    let declared = self.scopes.iter().any(|scope| scope.contains(&ident.name));
+                    if !declared && !matches!(ident.name, "undefined" | "NaN" | "Infinity") {
+                        let name = identifier_name(ident.name);
+                        return (format!("js_typeof_name(lambda: {name})"), Primary);
+                    }
+                }
+                let argument = self.expression_prec(&expr.argument, Lambda);
+                return (format!("js_typeof({argument})"), Primary);
+            }
+            UnaryOperator::Void | UnaryOperator::Delete => {
+                let argument = self.expression_prec(&expr.argument, Lambda);
+                return (format!("({argument}, None)[1]"), Primary);
+            }
+        };
+        let argument = self.expression_prec(&expr.argument, precedence);
+        (format!("{op}{argument}"), precedence)
+    }
+
+    fn argument(&mut self, argument: &'a Argument<'a>) -> String {
+        match argument {
+            Argument::SpreadElement(spread) => self.spread_element(spread),
+            Argument::Expression(expr) => self.expression_prec(expr, PyPrecedence::Lambda),
+        }
+    }
+
+    /// `...a` unpacks like `*a`
+    fn spread_element(&mut self, spread: &'a SpreadElement<'a>) -> String {
+        let argument = self.expression_prec(&spread.argument, PyPrecedence::Primary);
+        format!("*{argument}")
+    }
+
+    /// A `lambda` for concise bodies that only compute a value, otherwise a function defined
+    /// before the current statement
+    fn arrow_function(&mut self, arrow: &'a ArrowFunctionExpression<'a>) -> (String, PyPrecedence) {
+        if let [Statement::ExpressionStatement(stmt)] = arrow.body.statements.as_slice() {
+            let mut assigned = vec![];
+            collect_expression_assignments(&stmt.expression, &mut assigned);
+            let names = arrow
+                .params
+                .params
+                .iter()
+                .map(|param| match param {
+                    BindingPattern::BindingIdentifier(ident) => Some(identifier_name(ident.name)),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            // a lambda cannot be a coroutine
+            let simple = arrow.expression
+                && !arrow.is_async
+                && arrow.params.rest.is_none()
+                && assigned.is_empty();
+            if let (Some(names), true) = (names, simple) {
+                let len = self.code.len();
+                let body = self.expression_prec(&stmt.expression, PyPrecedence::Lambda);
+                if self.code.len() == len {
+                    let params = names
+                        .iter()
+                        .map(|name| format!(" {name}"))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    return (format!("lambda{params}: {body}"), PyPrecedence::Lambda);
+                }
+                // A function was defined for the body, outside the lambda parameters' scope
+                self.code.truncate(len);
+            }
+        }
+        let name = self.temp("fn");
+        let def = match arrow.expression {
+            true => Def::ConciseArrow,
+            false => Def::Function,
+        };
+        self.function_def(
+            &name,
+            &arrow.params,
+            &arrow.body.statements,
+            def,
+            arrow.is_async,
+        );
+        (name, PyPrecedence::Primary)
+    }
+
+    /// `++a` stores and yields `a + 1`, `a++` yields the stored value minus one again
+    fn update_expression(&mut self, update: &'a UpdateExpression<'a>) -> (String, PyPrecedence) {
+        let op = update_operator(update.operator);
+        let store = self.store(&update.argument, |_, current| format!("{current} {op} 1"));
+        if update.prefix {
+            return (store, PyPrecedence::Primary);
+        }
+        let undo = if op == "+" { "-" } else { "+" };
+        (format!("{store} {undo} 1"), PyPrecedence::Add)
+    }
+
+    /// An assignment in expression position, which Python only allows for plain names
+    fn assignment_expression(&mut self, assign: &'a AssignmentExpression<'a>) -> String {
+        self.store(&assign.left, |p, current| {
+            p.compound_value(assign.operator, &current, &assign.right)
+        })
+    }
+
+    /// Store the result of `value` into `target` in expression position, yielding it. `value` is
+    /// given the code for the current value of `target`, whose object and key are evaluated once.
+    fn store(
+        &mut self,
+        target: &'a AssignmentTarget<'a>,
+        value: impl FnOnce(&mut Self, String) -> String,
+    ) -> String {
+        match target {
+            // Destructured before the current statement, the assignment yields the value
+            AssignmentTarget::ArrayAssignmentTarget(_)
+            | AssignmentTarget::ObjectAssignmentTarget(_) => {
+                let value = value(self, String::new());
+                let temp = self.temp("ref");
+                self.line(&format!("{temp} = {value}"));
+                self.assignment_target_pattern(target, temp.clone());
+                temp
+            }
+            AssignmentTarget::Identifier(ident) => {
+                let name = identifier_name(ident.name);
+                let value = value(self, name.clone());
+                format!("({name} := {value})")
+            }
+            AssignmentTarget::StaticMemberExpression(member) => {
+                self.require(JS_SET);
+                let (object, reference) = self.evaluate_once(&member.object);
+                let name = identifier_name(member.property.name);
+                let value = value(self, format!("{reference}.{name}"));
+                format!("js_setattr({object}, '{name}', {value})")
+            }
+            AssignmentTarget::ComputedMemberExpression(member) => {
+                self.require(JS_SET);
+                let (object, reference) = self.evaluate_once(&member.object);
+                let (property, key) = self.evaluate_once(&member.expression);
+                let value = value(self, format!("{reference}[{key}]"));
+                format!("js_setitem({object}, {property}, {value})")
+            }
+        }
+    }
+
+    /// The code for the first use of `expr`, and for the later uses, which read a temporary
+    /// unless `expr` has no side effects
+    fn evaluate_once(&mut self, expr: &'a Expression<'a>) -> (String, String) {
+        let code = self.expression_prec(expr, PyPrecedence::Primary);
+        match expr {
+            Expression::Identifier(_)
+            | Expression::ThisExpression(_)
+            | Expression::NumericLiteral(_)
+            | Expression::StringLiteral(_) => (code.clone(), code),
+            _ => {
+                let temp = self.temp("ref");
+                (format!("({temp} := {code})"), temp)
+            }
+        }
+    }
+
+    /// The Python target for a simple assignment target, patterns use
+    /// [`Self::assignment_target_pattern`] instead
+    fn assignment_target(&mut self, target: &'a AssignmentTarget<'a>) -> String {
+        match target {
+            AssignmentTarget::ArrayAssignmentTarget(_)
+            | AssignmentTarget::ObjectAssignmentTarget(_) => {
+                unreachable!("only `=` and for-in/of loops assign to patterns")
+            }
+            AssignmentTarget::Identifier(ident) => identifier_name(ident.name),
+            AssignmentTarget::StaticMemberExpression(member) => {
+                let object = self.expression_prec(&member.object, PyPrecedence::Primary);
+                format!("{object}.{}", identifier_name(member.property.name))
+            }
+            AssignmentTarget::ComputedMemberExpression(member) => {
+                let object = self.expression_prec(&member.object, PyPrecedence::Primary);
+                let property = self.expression(&member.expression);
+                format!("{object}[{property}]")
+            }
+        }
+    }
+
+    /// The value stored by a compound assignment `target op= right`
+    fn compound_value(
+        &mut self,
+        operator: AssignmentOperator,
+        target: &str,
+        right: &'a Expression<'a>,
+    ) -> String {
+        use PyPrecedence::*;
+        let (op, precedence) = match operator {
+            AssignmentOperator::Assign => return self.expression_prec(right, Lambda),
+            AssignmentOperator::ShiftRightZeroFill => {
+                let right = self.expression_prec(right, Add);
+                return format!("({target} % 0x100000000) >> {right}");
+            }
+            AssignmentOperator::LogicalNullish => {
+                let right = self.expression_prec(right, Lambda);
+                return format!("{target} if {target} is not None else {right}");
+            }
+            AssignmentOperator::Addition => {
+                self.require(JS_ADD);
+                let right = self.expression_prec(right, Lambda);
+                return format!("js_add({target}, {right})");
+            }
+            AssignmentOperator::Remainder => {
+                self.require(JS_REMAINDER);
+                let right = self.expression_prec(right, Lambda);
+                return format!("js_remainder({target}, {right})");
+            }
+            AssignmentOperator::LogicalAnd => ("and", And),
+            AssignmentOperator::LogicalOr => ("or", Or),
+            operator => {
+                let op = augmented_operator(operator).unwrap_or_default();
+                let precedence = match op {
+                    "-" => Add,
+                    "*" | "/" => Multiply,
+                    "<<" | ">>" => Shift,
+                    "|" => BitwiseOr,
+                    "^" => BitwiseXor,
+                    "&" => BitwiseAnd,
+                    _ => Power,
+                };
+                (op, precedence)
+            }
+        };
+        let right = self.expression_prec(right, precedence.tighter());
+        format!("{target} {op} {right}")
+    }
+
+    /// An f-string, or `str.format` for substitutions that cannot be written inside one
+    fn template_literal(&mut self, template: &'a TemplateLiteral<'a>) -> String {
+        let cooked = |quasi: &TemplateElement| {
+            python_string(quasi.value.cooked.as_deref().unwrap_or_default())
+        };
+        if let [quasi] = template.quasis.as_slice() {
+            return cooked(quasi);
+        }
+        let values = template
+            .expressions
+            .iter()
+            .map(|expr| self.expression_prec(expr, PyPrecedence::Conditional))
+            .collect::<Vec<_>>();
+        // before Python 3.12, replacement fields cannot contain quotes, backslashes or comments
+        let inline = values
+            .iter()
+            .all(|value| !value.contains(['\'', '"', '\\', '#', '\n']));
+        let mut text = String::new();
+        for (i, quasi) in template.quasis.iter().enumerate() {
+            let string = cooked(quasi);
+            let string = &string[1..string.len() - 1];
+            text.push_str(&string.replace('{', "{{").replace('}', "}}"));
+            if let Some(value) = values.get(i) {
+                text.push('{');
+                if inline {
+                    text.push_str(value);
+                }
+                text.push('}');
+            }
+        }
+        if inline {
+            format!("f'{text}'")
+        } else {
+            format!("'{text}'.format({})", values.join(", "))
+        }
+    }
+
+    /// A link of an optional chain, where every object before a `?.` is stored in a temporary
+    /// and tested for `None` by one of the `guards`
+    fn chain_link(&mut self, expr: &'a Expression<'a>, guards: &mut Vec<String>) -> String {
+        match expr {
+            Expression::StaticMemberExpression(member) => {
+                let object = self.chain_object(&member.object, member.optional, guards);
+                static_member(object, member.property.name)
+            }
+            Expression::ComputedMemberExpression(member) => {
+                let object = self.chain_object(&member.object, member.optional, guards);
+                let property = self.expression(&member.expression);
+                format!("{object}[{property}]")
+            }
+            // Keep the special callees of `console.log()` and `a.push()` when possible
+            Expression::CallExpression(call)
+                if call.optional || has_optional_link(&call.callee) =>
+            {
+                let callee = self.chain_object(&call.callee, call.optional, guards);
+                let arguments = call
+                    .arguments
+                    .iter()
+                    .map(|argument| self.argument(argument))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{callee}({arguments})")
+            }
+            expr => self.expression_prec(expr, PyPrecedence::Primary),
+        }
+    }
+
+    fn chain_object(
+        &mut self,
+        object: &'a Expression<'a>,
+        optional: bool,
+        guards: &mut Vec<String>,
+    ) -> String {
+        let code = self.chain_link(object, guards);
+        if !optional {
+            return code;
+        }
+        let temp = self.temp("ref");
+        guards.push(format!("({temp} := {code}) is None"));
+        temp
+    }
+
+    fn callee(&mut self, callee: &'a Expression<'a>) -> String {
+        if let Expression::StaticMemberExpression(member) = callee {
+            match (&member.object, member.property.name) {
+                (Expression::Identifier(object), "log") if object.name == "console" => {
+                    return "print".into();
+                }
+                (object, "push") => {
+                    let object = self.expression_prec(object, PyPrecedence::Primary);
+                    return format!("{object}.append");
+                }
+                _ => {}
+            }
+        }
+        if let Expression::Super(_) = callee {
+            return "super().__init__".into();
+        }
+        self.expression_prec(callee, PyPrecedence::Primary)
+    }
+}
+
+fn static_member(object: String, name: &str) -> String {
+    match name {
+        "length" => format!("len({object})"),
+        name => format!("{object}.{}", identifier_name(name)),
+    }
+}
+
+/// Whether a `?.` is reachable through the objects and callees of a chain
+fn has_optional_link(expr: &Expression) -> bool {
+    match expr {
+        Expression::StaticMemberExpression(member) => {
+            member.optional || has_optional_link(&member.object)
+        }
+        Expression::ComputedMemberExpression(member) => {
+            member.optional || has_optional_link(&member.object)
+        }
+        Expression::CallExpression(call) => call.optional || has_optional_link(&call.callee),
+        _ => false,
+    }
+}
+
+/// Whether `expr` always evaluates to a number, so that `+` can be Python's
+fn is_number(expr: &Expression) -> bool {
+    match expr {
+        Expression::NumericLiteral(_) | Expression::UpdateExpression(_) => true,
+        Expression::UnaryExpression(unary) => matches!(
+            unary.operator,
+            UnaryOperator::UnaryNegation | UnaryOperator::UnaryPlus | UnaryOperator::BitwiseNot
+        ),
+        Expression::BinaryExpression(binary) => match binary.operator {
+            BinaryOperator::Addition => is_number(&binary.left) && is_number(&binary.right),
+            operator => matches!(
+                operator,
+                BinaryOperator::Subtraction
+                    | BinaryOperator::Multiplication
+                    | BinaryOperator::Division
+                    | BinaryOperator::Remainder
+                    | BinaryOperator::Exponential
+                    | BinaryOperator::ShiftLeft
+                    | BinaryOperator::ShiftRight
+                    | BinaryOperator::ShiftRightZeroFill
+                    | BinaryOperator::BitwiseOR
+                    | BinaryOperator::BitwiseXOR
+                    | BinaryOperator::BitwiseAnd
+            ),
+        },
+        Expression::ParenthesizedExpression(paren) => is_number(&paren.expression),
+        _ => false,
+    }
+}
+
+fn function_statements<'a>(func: &'a Function<'a>) -> &'a [Statement<'a>] {
+    func.body.as_ref().map_or(&[], |body| &body.statements)
+}
+
+/// Whether `stmt` is a `super(...)` call, after which `this` is initialized
+fn is_super_call(stmt: &Statement) -> bool {
+    matches!(stmt, Statement::ExpressionStatement(stmt)
+        if matches!(&stmt.expression, Expression::CallExpression(call)
+            if matches!(call.callee, Expression::Super(_))))
+}
+
+/// The Python operator for `op=` when Python has an augmented assignment that works like it
+fn augmented_operator(operator: AssignmentOperator) -> Option<&'static str> {
+    let op = match operator {
+        AssignmentOperator::Subtraction => "-",
+        AssignmentOperator::Multiplication => "*",
+        AssignmentOperator::Division => "/",
+        AssignmentOperator::ShiftLeft => "<<",
+        AssignmentOperator::ShiftRight => ">>",
+        AssignmentOperator::BitwiseOR => "|",
+        AssignmentOperator::BitwiseXOR => "^",
+        AssignmentOperator::BitwiseAnd => "&",
+        AssignmentOperator::Exponential => "**",
+        _ => return None,
+    };
+    Some(op)
+}
+
+fn update_operator(operator: UpdateOperator) -> &'static str {
+    match operator {
+        UpdateOperator::Increment => "+",
+        UpdateOperator::Decrement => "-",
+    }
+}
+
+fn identifier_name(name: &str) -> String {
+    if PYTHON_KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+/// A single quoted Python string literal with the value `text`
+fn python_string(text: &str) -> String {
+    let mut code = String::from("'");
+    for c in text.chars() {
+        match c {
+            '\\' => code.push_str("\\\\"),
+            '\'' => code.push_str("\\'"),
+            '\n' => code.push_str("\\n"),
+            '\r' => code.push_str("\\r"),
+            '\t' => code.push_str("\\t"),
+            c if c.is_control() => code.push_str(&format!("\\x{:02x}", c as u32)),
+            c => code.push(c),
+        }
+    }
+    code.push('\'');
+    code
+}
+
+/// A JavaScript regular expression pattern in Python's syntax, which names groups with
+/// `(?P<name>...)` and refers to them with `(?P=name)`
+fn python_regex_pattern(pattern: &str) -> String {
+    let mut code = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        if let Some((name, after)) = rest.strip_prefix("\\k<").and_then(|s| s.split_once('>')) {
+            code.push_str(&format!("(?P={name})"));
+            rest = after;
+        } else if rest.starts_with("(?<") && !rest[3..].starts_with(['=', '!']) {
+            code.push_str("(?P<");
+            rest = &rest[3..];
+        } else if c == '\\' {
+            // keep escapes whole, `\(?<` is not a group
+            let len = 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+            code.push_str(&rest[..len]);
+            rest = &rest[len..];
+        } else {
+            code.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    code
+}
+
+/// The Python module for an import source, `'./lib/a-b.js'` becomes `lib.a_b`
+fn module_name(source: &StringLiteral) -> String {
+    let text = source
+        .value
+        .get(1..source.value.len() - 1)
+        .unwrap_or_default();
+    let text = text
+        .strip_suffix(".mjs")
+        .or_else(|| text.strip_suffix(".js"))
+        .unwrap_or(text);
+    text.split('/')
+        .filter(|segment| !matches!(*segment, "" | "." | ".."))
+        .map(|segment| segment.trim_start_matches('@').replace(['-', '.'], "_"))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::IdentifierName(name) => identifier_name(name.name),
+        ModuleExportName::StringLiteral(lit) => {
+            identifier_name(lit.value.get(1..lit.value.len() - 1).unwrap_or_default())
+        }
+    }
+}
+
+/// The attribute name for a method or field
+fn class_member_name(key: &PropertyKey) -> String {
+    match key {
+        PropertyKey::IdentifierName(ident) => identifier_name(ident.name),
+        PropertyKey::StringLiteral(lit) => {
+            identifier_name(lit.value.get(1..lit.value.len() - 1).unwrap_or_default())
+        }
+        PropertyKey::NumericLiteral(lit) => format!("_{}", lit.raw),
+        PropertyKey::Computed(_) => unreachable!("computed members are added with `setattr`"),
+    }
+}
+
+/// Collect the names declared directly in a function body, without entering nested functions
+fn collect_declarations<'a>(stmts: &[Statement<'a>], names: &mut Vec<&'a str>) {
+    for stmt in stmts {
+        match stmt {
+            Statement::VariableDeclarationStatement(decl) => collect_declarators(decl, names),
+            Statement::FunctionDeclaration(func) => {
+                names.extend(func.id.as_ref().map(|id| id.name))
+            }
+            Statement::ClassDeclaration(class) => names.extend(class.id.as_ref().map(|id| id.name)),
+            Statement::ImportDeclaration(decl) => {
+                names.extend(decl.specifiers.iter().map(|specifier| match specifier {
+                    ImportDeclarationSpecifier::ImportSpecifier(specifier) => specifier.local.name,
+                    ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                        specifier.local.name
+                    }
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                        specifier.local.name
+                    }
+                }))
+            }
+            Statement::ExportNamedDeclaration(decl) => {
+                collect_declarations(decl.declaration.as_slice(), names)
+            }
+            Statement::ExportDefaultDeclaration(decl) => {
+                let id = match &decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(func) => func.id.as_ref(),
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => class.id.as_ref(),
+                    ExportDefaultDeclarationKind::Expression(_) => None,
+                };
+                names.push(id.map_or("default", |id| id.name));
+            }
+            Statement::BlockStatement(block) => collect_declarations(&block.body, names),
+            Statement::IfStatement(stmt) => {
+                collect_declarations(std::slice::from_ref(&stmt.consequent), names);
+                collect_declarations(stmt.alternate.as_slice(), names);
+            }
+            Statement::ForStatement(stmt) => {
+                if let Some(ForStatementInit::VariableDeclaration(decl)) = &stmt.init {
+                    collect_declarators(decl, names);
+                }
+                collect_declarations(std::slice::from_ref(&stmt.body), names);
+            }
+            Statement::ForInStatement(stmt) => {
+                if let ForStatementLeft::VariableDeclaration(decl) = &stmt.left {
+                    collect_declarators(decl, names);
+                }
+                collect_declarations(std::slice::from_ref(&stmt.body), names);
+            }
+            Statement::ForOfStatement(stmt) => {
+                if let ForStatementLeft::VariableDeclaration(decl) = &stmt.left {
+                    collect_declarators(decl, names);
+                }
+                collect_declarations(std::slice::from_ref(&stmt.body), names);
+            }
+            Statement::WhileStatement(stmt) => {
+                collect_declarations(std::slice::from_ref(&stmt.body), names)
+            }
+            Statement::DoWhileStatement(stmt) => {
+                collect_declarations(std::slice::from_ref(&stmt.body), names)
+            }
+            Statement::WithStatement(stmt) => {
+                collect_declarations(std::slice::from_ref(&stmt.body), names)
+            }
+            Statement::LabeledStatement(stmt) => {
+                collect_declarations(std::slice::from_ref(&stmt.body), names)
+            }
+            Statement::SwitchStatement(stmt) => {
+                for case in &stmt.cases {
+                    collect_declarations(&case.consequent, names);
+                }
+            }
+            Statement::TryStatement(stmt) => {
+                collect_declarations(&stmt.block.body, names);
+                if let Some(handler) = &stmt.handler {
+                    // `except ... as name` binds the parameter in the function scope
+                    if let Some(param) = &handler.param {
+                        collect_binding_names(param, names);
+                    }
+                    collect_declarations(&handler.body.body, names);
+                }
+                if let Some(finalizer) = &stmt.finalizer {
+                    collect_declarations(&finalizer.body, names);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_declarators<'a>(decl: &VariableDeclaration<'a>, names: &mut Vec<&'a str>) {
+    for declarator in &decl.declarations {
+        collect_binding_names(&declarator.id, names);
+    }
+}
+
+fn collect_binding_names<'a>(pattern: &BindingPattern<'a>, names: &mut Vec<&'a str>) {
+    match pattern {
+        BindingPattern::BindingIdentifier(ident) => names.push(ident.name),
+        BindingPattern::ObjectPattern(object) => {
+            for property in &object.properties {
+                collect_binding_names(&property.value, names);
+            }
+            if let Some(rest) = &object.rest {
+                collect_binding_names(&rest.argument, names);
+            }
+        }
+        BindingPattern::ArrayPattern(array) => {
+            for element in array.elements.iter().flatten() {
+                collect_binding_names(element, names);
+            }
+            if let Some(rest) = &array.rest {
+                collect_binding_names(&rest.argument, names);
+            }
+        }
+        BindingPattern::AssignmentPattern(pattern) => collect_binding_names(&pattern.left, names),
+    }
+}
+
+/// Collect the plain names an assignment stores to, including the names in patterns
+fn collect_assignment_target_names<'a>(target: &AssignmentTarget<'a>, names: &mut Vec<&'a str>) {
+    let mut maybe_default = |target: &AssignmentTargetMaybeDefault<'a>| match target {
+        AssignmentTargetMaybeDefault::AssignmentTarget(target) => {
+            collect_assignment_target_names(target, names)
+        }
+        AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => {
+            collect_assignment_target_names(&target.binding, names)
+        }
+    };
+    match target {
+        AssignmentTarget::Identifier(ident) => names.push(ident.name),
+        AssignmentTarget::ArrayAssignmentTarget(array) => {
+            array.elements.iter().flatten().for_each(maybe_default)
+        }
+        AssignmentTarget::ObjectAssignmentTarget(object) => {
+            object
+                .properties
+                .iter()
+                .for_each(|property| maybe_default(&property.value));
+            if let Some(rest) = &object.rest {
+                collect_assignment_target_names(&rest.target, names);
+            }
+        }
+        AssignmentTarget::StaticMemberExpression(_)
+        | AssignmentTarget::ComputedMemberExpression(_) => {}
+    }
+}
+
+fn is_pattern(target: &AssignmentTarget) -> bool {
+    matches!(
+        target,
+        AssignmentTarget::ArrayAssignmentTarget(_) | AssignmentTarget::ObjectAssignmentTarget(_)
+    )
+}
+
+/// Collect the plain names assigned to in a statement, without entering nested functions
+fn collect_assignments<'a>(stmt: &'a Statement<'a>, names: &mut Vec<&'a str>) {
+    use collect_expression_assignments as expr;
+    match stmt {
+        Statement::ExpressionStatement(stmt) => expr(&stmt.expression, names),
+        Statement::ReturnStatement(stmt) => {
+            if let Some(argument) = &stmt.argument {
+                expr(argument, names);
+            }
+        }
+        Statement::VariableDeclarationStatement(decl) => {
+            for init in decl.declarations.iter().filter_map(|d| d.init.as_ref()) {
+                expr(init, names);
+            }
+        }
+        Statement::IfStatement(stmt) => {
+            expr(&stmt.test, names);
+            collect_assignments(&stmt.consequent, names);
+            if let Some(alternate) = &stmt.alternate {
+                collect_assignments(alternate, names);
+            }
+        }
+        Statement::ForStatement(stmt) => {
+            match &stmt.init {
+                Some(ForStatementInit::VariableDeclaration(decl)) => {
+                    for init in decl.declarations.iter().filter_map(|d| d.init.as_ref()) {
+                        expr(init, names);
+                    }
+                }
+                Some(ForStatementInit::Expression(init)) => expr(init, names),
+                None => {}
+            }
+            for test_or_update in stmt.test.iter().chain(&stmt.update) {
+                expr(test_or_update, names);
+            }
+            collect_assignments(&stmt.body, names);
+        }
+        Statement::ForInStatement(stmt) => {
+            if let ForStatementLeft::AssignmentTarget(target) = &stmt.left {
+                collect_assignment_target_names(target, names);
+            }
+            expr(&stmt.right, names);
+            collect_assignments(&stmt.body, names);
+        }
+        Statement::ForOfStatement(stmt) => {
+            if let ForStatementLeft::AssignmentTarget(target) = &stmt.left {
+                collect_assignment_target_names(target, names);
+            }
+            expr(&stmt.right, names);
+            collect_assignments(&stmt.body, names);
+        }
+        Statement::WhileStatement(stmt) => {
+            expr(&stmt.test, names);
+            collect_assignments(&stmt.body, names);
+        }
+        Statement::DoWhileStatement(stmt) => {
+            expr(&stmt.test, names);
+            collect_assignments(&stmt.body, names);
+        }
+        Statement::WithStatement(stmt) => {
+            expr(&stmt.object, names);
+            collect_assignments(&stmt.body, names);
+        }
+        Statement::LabeledStatement(stmt) => collect_assignments(&stmt.body, names),
+        Statement::SwitchStatement(stmt) => {
+            expr(&stmt.discriminant, names);
+            for case in &stmt.cases {
+                if let Some(test) = &case.test {
+                    expr(test, names);
+                }
+                for stmt in &case.consequent {
+                    collect_assignments(stmt, names);
+                }
+            }
+        }
+        Statement::BlockStatement(block) => {
+            for stmt in &block.body {
+                collect_assignments(stmt, names);
+            }
+        }
+        Statement::ThrowStatement(stmt) => expr(&stmt.argument, names),
+        Statement::ExportNamedDeclaration(decl) => {
+            if let Some(declaration) = &decl.declaration {
+                collect_assignments(declaration, names);
+            }
+        }
+        Statement::ExportDefaultDeclaration(decl) => {
+            if let ExportDefaultDeclarationKind::Expression(expression) = &decl.declaration {
+                expr(expression, names);
+            }
+        }
+        Statement::TryStatement(stmt) => {
+            let blocks = std::iter::once(&stmt.block)
+                .chain(stmt.handler.as_ref().map(|handler| &handler.body))
+                .chain(&stmt.finalizer);
+            for block in blocks {
+                for stmt in &block.body {
+                    collect_assignments(stmt, names);
+                }
+            }
+        }
+        Statement::EmptyStatement(_)
+        | Statement::BreakStatement(_)
+        | Statement::ContinueStatement(_)
+        | Statement::FunctionDeclaration(_)
+        | Statement::ClassDeclaration(_)
+        | Statement::ImportDeclaration(_)
+        | Statement::ExportAllDeclaration(_)
+        | Statement::Error(_) => {}
+    }
+}
+
+fn collect_argument_assignments<'a>(argument: &'a Argument<'a>, names: &mut Vec<&'a str>) {
+    match argument {
+        Argument::SpreadElement(spread) => collect_expression_assignments(&spread.argument, names),
+        Argument::Expression(expr) => collect_expression_assignments(expr, names),
+    }
+}
+
+fn collect_expression_assignments<'a>(expr: &'a Expression<'a>, names: &mut Vec<&'a str>) {
+    match expr {
+        Expression::AssignmentExpression(assign) => {
+            collect_assignment_target_names(&assign.left, names);
+            collect_expression_assignments(&assign.right, names);
+        }
+        Expression::SequenceExpression(seq) => {
+            for expr in &seq.expressions {
+                collect_expression_assignments(expr, names);
+            }
+        }
+        Expression::BinaryExpression(expr) => {
+            collect_expression_assignments(&expr.left, names);
+            collect_expression_assignments(&expr.right, names);
+        }
+        Expression::LogicalExpression(expr) => {
+            collect_expression_assignments(&expr.left, names);
+            collect_expression_assignments(&expr.right, names);
+        }
+        Expression::UpdateExpression(expr) => {
+            if let AssignmentTarget::Identifier(ident) = &expr.argument {
+                names.push(ident.name);
+            }
+        }
+        Expression::ConditionalExpression(expr) => {
+            collect_expression_assignments(&expr.test, names);
+            collect_expression_assignments(&expr.consequent, names);
+            collect_expression_assignments(&expr.alternate, names);
+        }
+        Expression::UnaryExpression(expr) => collect_expression_assignments(&expr.argument, names),
+        Expression::AwaitExpression(expr) => collect_expression_assignments(&expr.argument, names),
+        Expression::YieldExpression(expr) => {
+            if let Some(argument) = &expr.argument {
+                collect_expression_assignments(argument, names);
+            }
+        }
+        Expression::ArrayExpression(array) => {
+            for element in &array.elements {
+                match element {
+                    ArrayExpressionElement::Expression(expr) => {
+                        collect_expression_assignments(expr, names)
+                    }
+                    ArrayExpressionElement::SpreadElement(spread) => {
+                        collect_expression_assignments(&spread.argument, names)
+                    }
+                    ArrayExpressionElement::Elision(_) => {}
+                }
+            }
+        }
+        Expression::ObjectExpression(object) => {
+            for property in &object.properties {
+                match property {
+                    ObjectPropertyKind::ObjectProperty(property) => {
+                        if let PropertyKey::Computed(key) = &property.key {
+                            collect_expression_assignments(key, names);
+                        }
+                        collect_expression_assignments(&property.value, names);
+                    }
+                    ObjectPropertyKind::SpreadProperty(spread) => {
+                        collect_expression_assignments(&spread.argument, names)
+                    }
+                }
+            }
+        }
+        Expression::StaticMemberExpression(member) => {
+            collect_expression_assignments(&member.object, names)
+        }
+        Expression::ComputedMemberExpression(member) => {
+            collect_expression_assignments(&member.object, names);
+            collect_expression_assignments(&member.expression, names);
+        }
+        Expression::CallExpression(call) => {
+            collect_expression_assignments(&call.callee, names);
+            for argument in &call.arguments {
+                collect_argument_assignments(argument, names);
+            }
+        }
+        Expression::NewExpression(new) => {
+            collect_expression_assignments(&new.callee, names);
+            for argument in &new.arguments {
+                collect_argument_assignments(argument, names);
+            }
+        }
+        Expression::ImportExpression(expr) => collect_expression_assignments(&expr.source, names),
+        Expression::TemplateLiteral(template) => {
+            for expr in &template.expressions {
+                collect_expression_assignments(expr, names);
+            }
+        }
+        Expression::TaggedTemplateExpression(expr) => {
+            collect_expression_assignments(&expr.tag, names);
+            for expr in &expr.quasi.expressions {
+                collect_expression_assignments(expr, names);
+            }
+        }
+        Expression::ChainExpression(chain) => {
+            collect_expression_assignments(&chain.expression, names)
+        }
+        Expression::ParenthesizedExpression(paren) => {
+            collect_expression_assignments(&paren.expression, names)
+        }
+        Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::NumericLiteral(_)
+        | Expression::StringLiteral(_)
+        | Expression::RegExpLiteral(_)
+        | Expression::Identifier(_)
+        | Expression::ThisExpression(_)
+        | Expression::Super(_)
+        | Expression::MetaProperty(_)
+        // assignments in nested functions belong to their own scope
+        | Expression::FunctionExpression(_)
+        | Expression::ArrowFunctionExpression(_)
+        | Expression::ClassExpression(_) => {}
+    }
+}
+
+/// Collect the jumps in `stmt` that leave the statement lowered to the innermost loop, which
+/// unlabeled `break` targets if `breaks` and unlabeled `continue` targets if `continues`.
+/// `labels` are the labels defined within that statement, including its own.
+fn collect_escaping_jumps<'a>(
+    stmt: &'a Statement<'a>,
+    labels: &mut Vec<&'a str>,
+    breaks: bool,
+    continues: bool,
+    jumps: &mut Vec<Jump<'a>>,
+) {
+    let mut collect = |stmt: &'a Statement<'a>, labels: &mut Vec<&'a str>, breaks, continues| {
+        collect_escaping_jumps(stmt, labels, breaks, continues, jumps)
+    };
+    let jump = match stmt {
+        Statement::BreakStatement(stmt) => Jump::Break(stmt.label.as_ref().map(|l| l.name)),
+        Statement::ContinueStatement(stmt) => Jump::Continue(stmt.label.as_ref().map(|l| l.name)),
+        Statement::LabeledStatement(stmt) => {
+            labels.push(stmt.label.name);
+            collect(&stmt.body, labels, breaks, continues);
+            labels.pop();
+            return;
+        }
+        Statement::BlockStatement(block) => {
+            for stmt in &block.body {
+                collect(stmt, labels, breaks, continues);
+            }
+            return;
+        }
+        Statement::IfStatement(stmt) => {
+            for stmt in std::iter::once(&stmt.consequent).chain(&stmt.alternate) {
+                collect(stmt, labels, breaks, continues);
+            }
+            return;
+        }
+        Statement::WithStatement(stmt) => return collect(&stmt.body, labels, breaks, continues),
+        Statement::TryStatement(stmt) => {
+            let blocks = std::iter::once(&stmt.block)
+                .chain(stmt.handler.as_ref().map(|handler| &handler.body))
+                .chain(&stmt.finalizer);
+            for stmt in blocks.flat_map(|block| &block.body) {
+                collect(stmt, labels, breaks, continues);
+            }
+            return;
+        }
+        Statement::SwitchStatement(stmt) => {
+            for stmt in stmt.cases.iter().flat_map(|case| &case.consequent) {
+                collect(stmt, labels, true, continues);
+            }
+            return;
+        }
+        Statement::ForStatement(stmt) => return collect(&stmt.body, labels, true, true),
+        Statement::ForInStatement(stmt) => return collect(&stmt.body, labels, true, true),
+        Statement::ForOfStatement(stmt) => return collect(&stmt.body, labels, true, true),
+        Statement::WhileStatement(stmt) => return collect(&stmt.body, labels, true, true),
+        Statement::DoWhileStatement(stmt) => return collect(&stmt.body, labels, true, true),
+        _ => return,
+    };
+    let escapes = match jump {
+        Jump::Break(None) => !breaks,
+        Jump::Continue(None) => !continues,
+        Jump::Break(Some(label)) | Jump::Continue(Some(label)) => !labels.contains(&label),
+    };
+    if escapes && !jumps.contains(&jump) {
+        jumps.push(jump);
+    }
+}
+
+/// The value an escape flag is set to for `jump`
+fn jump_value(jump: Jump) -> String {
+    match jump {
+        Jump::Break(None) => "break".to_string(),
+        Jump::Continue(None) => "continue".to_string(),
+        Jump::Break(Some(label)) => format!("break {label}"),
+        Jump::Continue(Some(label)) => format!("continue {label}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn transpile(source: &str) -> String {
+        let mut parser = Parser::new(source);
+        let program = parser.parse().unwrap();
+        PythonCodegen::new().build(&program)
+    }
+
+    /// Run the translation of `source` with `python3`, and return what it printed
+    fn run(source: &str) -> String {
+        let output = std::process::Command::new("python3")
+            .arg("-c")
+            .arg(transpile(source))
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "{stderr}");
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn function_declaration_test() {
+        let code = transpile("function add(a, b) { return a + b * 2 }");
+        assert!(code.starts_with("def js_string(value):"));
+        assert!(code.ends_with("def add(a, b):\n    return js_add(a, b * 2)\n"));
+    }
+
+    #[test]
+    fn if_else_chain_test() {
+        let code = transpile("if (a) { b = 1 } else if (c) {} else { d(!a) }");
+        assert_eq!(
+            code,
+            "if a:\n    b = 1\nelif c:\n    pass\nelse:\n    d(not a)\n"
+        );
+    }
+
+    #[test]
+    fn for_statement_continue_runs_update_test() {
+        let code = transpile("for (var i = 0; i < 3; i += 1) { if (i) continue; }");
+        assert!(code.ends_with(
+            "i = 0\nwhile i < 3:\n    if i:\n        i = js_add(i, 1)\n        continue\n    i = js_add(i, 1)\n"
+        ));
+    }
+
+    #[test]
+    fn switch_statement_test() {
+        let code = transpile("switch (a) { case 1: b(); break; default: c() }");
+        assert_eq!(
+            code,
+            "_switch_0 = a\n\
+             _fallthrough_1 = False\n\
+             while True:\n    \
+                 if _fallthrough_1 or _switch_0 == 1:\n        \
+                     _fallthrough_1 = True\n        \
+                     b()\n        \
+                     break\n    \
+                 if _fallthrough_1 or not (_switch_0 == 1):\n        \
+                     _fallthrough_1 = True\n        \
+                     c()\n    \
+                 break\n"
+        );
+    }
+
+    #[test]
+    fn operators_test() {
+        assert_eq!(transpile("a === b && c !== d;"), "a == b and c != d\n");
+        assert_eq!(transpile("(a < b) < c;"), "(a < b) < c\n");
+        assert_eq!(transpile("a - (b - c);"), "a - (b - c)\n");
+        assert_eq!(transpile("x = a instanceof B;"), "x = isinstance(a, B)\n");
+        assert_eq!(transpile("f(x = 1);"), "f((x := 1))\n");
+        assert_eq!(
+            transpile("x = a ? b : c ? d : e;"),
+            "x = b if a else d if c else e\n"
+        );
+        assert_eq!(
+            transpile("x = (a ? b : c) ? d || e : f;"),
+            "x = d or e if (b if a else c) else f\n"
+        );
+        assert_eq!(
+            transpile("a.b++; x = ++i + j--;"),
+            "a.b += 1\nx = (i := i + 1) + ((j := j - 1) + 1)\n"
+        );
+        assert_eq!(
+            transpile("x = (-a) ** b ** -c; x **= 2; a &&= b; a ||= c ?? d;"),
+            "x = (-a) ** b ** -c\nx **= 2\na = a and b\na = a or (c if c is not None else d)\n"
+        );
+    }
+
+    #[test]
+    fn member_assignment_value_test() {
+        assert_eq!(
+            transpile("x = o.a++; y = f()[k] -= 2;")
+                .split_once("\n\nx")
+                .unwrap()
+                .1,
+            " = js_setattr(o, 'a', o.a + 1) - 1\ny = js_setitem((_ref_0 := f()), k, _ref_0[k] - 2)\n"
+        );
+        let source = "var o = { a: 1 }, arr = [1], n = 0;
+            function f() { n++; return o; }
+            var y = (o.a = 5), z = arr[0] += 2, x = o.a++, w = f().a++, v = --f()['a'];
+            console.log(y, z, x, w, v, o.a, arr[0], n);";
+        assert_eq!(run(source), "5 3 5 6 6 6 3 2\n");
+        let source = "var o = { a: 's' }, n = 0; function f() { n++; return o; }
+            f().a += 1; f()['a'] ??= 2; console.log(o.a, n);";
+        assert_eq!(run(source), "s1 2\n");
+    }
+
+    #[test]
+    fn typeof_test() {
+        let source = "var s = 'x'; function f(a) { return typeof a; }
+            console.log(typeof s === 'string', typeof u === 'undefined', typeof f);
+            console.log(typeof 1.5, typeof true, typeof {}, typeof [], f(undefined));";
+        assert_eq!(
+            run(source),
+            "True True function\nnumber boolean object object undefined\n"
+        );
+        assert!(transpile("x = typeof u;").ends_with("x = js_typeof_name(lambda: u)\n"));
+    }
+
+    #[test]
+    fn addition_and_remainder_test() {
+        let source = "var s = 'n=' + 1, t = [1, 2] + '', u = 1 + 2 * 3, v = 1.5 + 1.5;
+            s += true; v %= 2;
+            console.log(s, t, u, v, 7 % -3, -7 % 3, 5.5 % 2);";
+        assert_eq!(run(source), "n=1true 1,2 7 1.0 1 -1 1.5\n");
+        // both operands are known to be numbers
+        assert_eq!(transpile("x = 1 + -a;"), "x = 1 + -a\n");
+    }
+
+    #[test]
+    fn variable_declaration_test() {
+        assert_eq!(
+            transpile("var a = 1, b, c = b = a;"),
+            "a = 1\nb = None\nc = b = a\n"
+        );
+    }
+
+    #[test]
+    fn for_in_of_statement_test() {
+        let code = transpile("for (const k in o) f(k); for (x.y of a) { continue }");
+        assert!(code.starts_with("def js_keys(value):"));
+        assert!(code.ends_with("for k in js_keys(o):\n    f(k)\nfor x.y in a:\n    continue\n"));
+    }
+
+    #[test]
+    fn labeled_statement_test() {
+        let code = transpile(
+            "a: while (b) { for (;;) { if (c) continue a; break a; } d: { if (e) break d; continue; } f(); }",
+        );
+        assert_eq!(
+            code,
+            "while b:\n    _jump_0 = None\n    while True:\n        if c:\n            _jump_0 = 'continue a'\n            break\n        _jump_0 = 'break a'\n        break\n    if _jump_0 == 'continue a':\n        continue\n    if _jump_0 == 'break a':\n        break\n    _jump_1 = None\n    while True:\n        if e:\n            break\n        _jump_1 = 'continue'\n        break\n        break\n    if _jump_1 == 'continue':\n        continue\n    f()\n"
+        );
+        // Labels that are never jumped to are dropped
+        assert_eq!(transpile("a: { b(); }"), "b()\n");
+    }
+
+    #[test]
+    fn nonlocal_and_global_test() {
+        let code = transpile("var n; function f() { var c; function g() { c = 1; n = 2 } }");
+        assert_eq!(
+            code,
+            "n = None\ndef f():\n    c = None\n    def g():\n        nonlocal c\n        global n\n        c = 1\n        n = 2\n"
+        );
+    }
+
+    #[test]
+    fn try_statement_test() {
+        let code = transpile("try { throw 1 } catch (e) { f(e) } finally { g() } try {} catch {}");
+        assert!(code.starts_with("class JSException(Exception):"));
+        assert!(code.ends_with(
+            "try:\n    raise js_throw(1)\nexcept Exception as e:\n    e = js_catch(e)\n    f(e)\nfinally:\n    g()\ntry:\n    pass\nexcept Exception:\n    pass\n"
+        ));
+    }
+
+    #[test]
+    fn new_expression_test() {
+        assert_eq!(
+            transpile("x = new a.B(1, this); throw new Error('m');")
+                .lines()
+                .last(),
+            Some("raise js_throw(Exception('m'))")
+        );
+        assert!(transpile("x = new a.B(1, this);").ends_with("x = a.B(1, self)\n"));
+    }
+
+    #[test]
+    fn function_expression_test() {
+        assert!(
+            transpile("f(x => x + 1, () => g(), (a, b) => a ? b : (c) => c);").ends_with(
+                "\nf(lambda x: js_add(x, 1), lambda: g(), lambda a, b: b if a else lambda c: c)\n"
+            )
+        );
+        assert_eq!(
+            transpile("var h = function (a) { return a }; k = (a) => { a = 1 }; m = () => n = 1;"),
+            "def _fn_0(a):\n    return a\nh = _fn_0\ndef _fn_1(a):\n    a = 1\nk = _fn_1\ndef _fn_2():\n    global n\n    return (n := 1)\nm = _fn_2\n"
+        );
+    }
+
+    #[test]
+    fn destructuring_test() {
+        let code = transpile("const { a, b: [c, , d = 1], ...e } = f(); [a.x, c] = [c, a];");
+        assert!(code.starts_with("def js_property(value, key):"));
+        assert!(code.ends_with(
+            "_ref_0 = f()\na = js_property(_ref_0, 'a')\n_ref_1 = js_property(_ref_0, 'b')\nc = js_element(_ref_1, 0)\nd = js_element(_ref_1, 2)\nif d is None:\n    d = 1\ne = js_rest(_ref_0, ['a', 'b'])\n_ref_2 = [c, a]\na.x = js_element(_ref_2, 0)\nc = js_element(_ref_2, 1)\n"
+        ));
+        assert_eq!(
+            transpile("function g([a], b = 1) { var c; ({ c: c } = a); } for (const [k, v] of m) {}")
+                .split_once("\n\ndef g")
+                .unwrap()
+                .1,
+            "(_arg_0, b=None):\n    a = js_element(_arg_0, 0)\n    if b is None:\n        b = 1\n    c = None\n    c = js_property(a, 'c')\nfor _item_1 in m:\n    k = js_element(_item_1, 0)\n    v = js_element(_item_1, 1)\n"
+        );
+    }
+
+    #[test]
+    fn spread_and_rest_test() {
+        let code = transpile("f(...a, b); x = [1, ...(c || d)]; [e, ...g] = x;");
+        assert!(code.ends_with("f(*a, b)\nx = [1, *(c or d)]\ne = js_element(x, 0)\ng = x[1:]\n"));
+        assert_eq!(
+            transpile("function h(a, ...[b]) {} k = (...c) => c;")
+                .split_once("\ndef h")
+                .unwrap()
+                .1,
+            "(a, *_arg_0):\n    _arg_0 = list(_arg_0)\n    b = js_element(_arg_0, 0)\ndef _fn_1(*c):\n    c = list(c)\n    return c\nk = _fn_1\n"
+        );
+    }
+
+    #[test]
+    fn template_literal_test() {
+        assert_eq!(
+            transpile("x = `${a} {b}\\n`; y = `it's ${c ? 'd' : e}`; z = f`g${h}\\u`;"),
+            "x = f'{a} {{b}}\\n'\ny = 'it\\'s {}'.format('d' if c else e)\nz = f(['g', None], h)\n"
+        );
+    }
+
+    #[test]
+    fn regexp_literal_test() {
+        assert_eq!(
+            transpile("x = /(?<y>\\d+)\\k<y>(?<=a)\\(?<z)/gim;"),
+            "import re as js_re\n\nx = js_re.compile('(?P<y>\\\\d+)(?P=y)(?<=a)\\\\(?<z)', js_re.I | js_re.M)\n"
+        );
+    }
+
+    #[test]
+    fn optional_chain_test() {
+        assert_eq!(
+            transpile("x = a?.b.length; y = a.b?.[c]?.(d) ?? e; console?.log(f()?.g);"),
+            "x = None if (_ref_0 := a) is None else len(_ref_0.b)\n\
+             y = _ref_3 if (_ref_3 := None if (_ref_1 := a.b) is None or (_ref_2 := _ref_1[c]) is None else _ref_2(d)) is not None else e\n\
+             None if (_ref_4 := console) is None else _ref_4.log(None if (_ref_5 := f()) is None else _ref_5.g)\n"
+        );
+    }
+
+    #[test]
+    fn class_test() {
+        let code = transpile(
+            "class A extends B { x = 1; static y = 2; constructor(a) { super(a); this.a = a } get z() { return super.z } static { f(this) } }",
+        );
+        assert_eq!(
+            code,
+            "class A(B):\n    y = 2\n    def __init__(self, a):\n        super().__init__(a)\n        self.x = 1\n        self.a = a\n    @property\n    def z(self):\n        return super().z\ndef _static_0(self):\n    f(self)\n_static_0(A)\n"
+        );
+        assert_eq!(
+            transpile("x = class { y; static m() {} };"),
+            "class _cls_0:\n    def __init__(self, *args, **kwargs):\n        self.y = None\n    @classmethod\n    def m(self):\n        pass\nx = _cls_0\n"
+        );
+    }
+
+    #[test]
+    fn object_literal_test() {
+        let code = transpile("x = { a, [k]: 1, ...b }; ({ a, ...r } = x);");
+        assert!(code.ends_with(
+            "x = JSObject({'a': a, k: 1, **b})\na = js_property(x, 'a')\nr = js_rest(x, ['a'])\n"
+        ));
+        let code =
+            transpile("x = { m() { return this.a }, get c() { return 1 }, set [k](v) {}, d: 2 };");
+        assert!(code.ends_with(
+            "class _obj_0(JSObject):\n    def m(self):\n        return self.a\n    @property\n    def c(self):\n        return 1\n    def _member_1(self, v):\n        pass\nsetattr(_obj_0, k, property(None, vars(_obj_0)['_member_1']))\nx = _obj_0({'d': 2})\n"
+        ));
+        assert_eq!(
+            transpile("class A { [k] = 1; static [j]; [m]() {} }"),
+            "class A:\n    def __init__(self, *args, **kwargs):\n        setattr(self, k, 1)\n    def _member_0(self):\n        pass\nsetattr(A, j, None)\nsetattr(A, m, vars(A)['_member_0'])\n"
+        );
+    }
+
+    #[test]
+    fn async_and_generator_test() {
+        assert_eq!(
+            transpile("async function f(a) { const b = await a.c(); x = async () => await b; }"),
+            "async def f(a):\n    global x\n    b = await a.c()\n    async def _fn_0():\n        return await b\n    x = _fn_0\n"
+        );
+        assert_eq!(
+            transpile("function* g() { const r = yield 1; yield* [r]; return f(yield); }"),
+            "def g():\n    r = (yield 1)\n    yield from [r]\n    return f((yield))\n"
+        );
+    }
+
+    #[test]
+    fn module_test() {
+        let mut parser = Parser::new(
+            "import a, { b as c, d } from './lib/e-f.js'; import * as g from 'h'; import 'i';\n\
+             export { a as j } from 'k'; export * from 'l'; export { d as m };\n\
+             export default class {} x = import('./n.js');",
+        );
+        let program = parser.parse_module().unwrap();
+        assert_eq!(
+            PythonCodegen::new().build(&program),
+            "from lib.e_f import default as a, b as c, d\nimport h as g\nimport i\n\
+             from k import a as j\nfrom l import *\nm = d\n\
+             class default:\n    pass\nx = __import__('importlib').import_module('n')\n"
+        );
+    }
+
+    #[test]
+    fn member_and_call_test() {
+        let code = transpile("console.log(a.length, b[0]); a.push({ k: 1 });");
+        assert!(code.starts_with("class JSObject(dict):"));
+        assert!(code.ends_with("print(len(a), b[0])\na.append(JSObject({'k': 1}))\n"));
+    }
+}
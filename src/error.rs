@@ -0,0 +1,322 @@
+use std::fmt;
+
+use js_lexer::error::LexErrorKind;
+use js_lexer::token::Type;
+
+use crate::ast::Span;
+
+/// An error collected by [`Parser::parse_recovering`](crate::Parser::parse_recovering)
+pub type Diagnostic = ParseError;
+
+/// Errors reported by the [`Parser`](crate::Parser).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// A token that cannot start or continue the construct being parsed
+    UnexpectedToken { span: Span, found: Type },
+    /// A specific token is required by the grammar
+    ExpectedToken {
+        span: Span,
+        expected: Type,
+        found: Type,
+    },
+    /// A statement is not terminated by a semicolon and none can be inserted
+    MissingSemicolon { span: Span },
+    /// The left-hand side of an assignment is not assignable
+    InvalidAssignmentTarget { span: Span },
+    /// A numeric literal whose value cannot be computed
+    InvalidNumber { span: Span },
+    /// `()` outside of an arrow function
+    EmptyParenthesizedExpression { span: Span },
+    /// An arrow function parameter list that is not a list of identifiers
+    InvalidArrowParameters { span: Span },
+    /// A line break between `throw` and its expression
+    NewlineAfterThrow { span: Span },
+    /// `import`, `export` or `import.meta` in a script
+    ModuleSyntaxOutsideModule { span: Span },
+    /// `import` or `export` declaration nested in a block or function
+    ModuleDeclarationNotTopLevel { span: Span },
+    /// A `for-in` or `for-of` head declaring several variables or an initializer
+    InvalidForInOfDeclaration { span: Span },
+    /// A destructuring declaration without `= value`, outside of a for-in or for-of loop
+    MissingDestructuringInitializer { span: Span },
+    /// A malformed escape sequence in a template literal that is not tagged
+    InvalidTemplateEscape { span: Span },
+    /// An unknown or repeated regular expression flag, or both `u` and `v`
+    InvalidRegExpFlag { span: Span },
+    /// `??` next to `||` or `&&` without parentheses around one of them
+    MixedCoalesce { span: Span },
+    /// A unary operator applied to the left operand of `**` without parentheses, `-a ** b`
+    UnaryBeforeExponentiation { span: Span },
+    /// `{ a = 1 }` in an object literal that is not a destructuring pattern
+    InvalidShorthandInitializer { span: Span },
+    /// A `break` or `continue` label that no enclosing statement has
+    UndefinedLabel { span: Span },
+    /// A label that an enclosing statement already has
+    DuplicateLabel { span: Span },
+    /// `continue` with the label of a statement that is not a loop
+    InvalidContinueLabel { span: Span },
+    /// `new.target` outside of a non-arrow function
+    NewTargetOutsideFunction { span: Span },
+    /// A parameter name that is bound twice by a function in strict mode code
+    DuplicateParameter { span: Span },
+    /// `delete` of a plain identifier in strict mode code
+    DeleteOfIdentifier { span: Span },
+    /// A second `constructor` method in a class body
+    DuplicateConstructor { span: Span },
+    /// The lexer could not produce a token
+    Lex { span: Span, kind: LexErrorKind },
+}
+
+impl ParseError {
+    /// A stable identifier for this kind of error
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnexpectedToken { .. } => "E0001",
+            Self::ExpectedToken { .. } => "E0002",
+            Self::MissingSemicolon { .. } => "E0003",
+            Self::InvalidAssignmentTarget { .. } => "E0004",
+            Self::InvalidNumber { .. } => "E0005",
+            Self::EmptyParenthesizedExpression { .. } => "E0006",
+            Self::Lex { .. } => "E0007",
+            Self::NewlineAfterThrow { .. } => "E0008",
+            Self::InvalidArrowParameters { .. } => "E0009",
+            Self::ModuleSyntaxOutsideModule { .. } => "E0010",
+            Self::ModuleDeclarationNotTopLevel { .. } => "E0011",
+            Self::InvalidForInOfDeclaration { .. } => "E0012",
+            Self::MissingDestructuringInitializer { .. } => "E0013",
+            Self::InvalidTemplateEscape { .. } => "E0014",
+            Self::InvalidRegExpFlag { .. } => "E0015",
+            Self::MixedCoalesce { .. } => "E0016",
+            Self::UnaryBeforeExponentiation { .. } => "E0017",
+            Self::InvalidShorthandInitializer { .. } => "E0018",
+            Self::UndefinedLabel { .. } => "E0019",
+            Self::DuplicateLabel { .. } => "E0020",
+            Self::InvalidContinueLabel { .. } => "E0021",
+            Self::NewTargetOutsideFunction { .. } => "E0022",
+            Self::DuplicateParameter { .. } => "E0023",
+            Self::DeleteOfIdentifier { .. } => "E0024",
+            Self::DuplicateConstructor { .. } => "E0025",
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Self::UnexpectedToken { span, .. }
+            | Self::ExpectedToken { span, .. }
+            | Self::MissingSemicolon { span }
+            | Self::InvalidAssignmentTarget { span }
+            | Self::InvalidNumber { span }
+            | Self::EmptyParenthesizedExpression { span }
+            | Self::Lex { span, .. }
+            | Self::NewlineAfterThrow { span }
+            | Self::InvalidArrowParameters { span }
+            | Self::ModuleSyntaxOutsideModule { span }
+            | Self::ModuleDeclarationNotTopLevel { span }
+            | Self::InvalidForInOfDeclaration { span }
+            | Self::MissingDestructuringInitializer { span }
+            | Self::InvalidTemplateEscape { span }
+            | Self::InvalidRegExpFlag { span }
+            | Self::MixedCoalesce { span }
+            | Self::UnaryBeforeExponentiation { span }
+            | Self::InvalidShorthandInitializer { span }
+            | Self::UndefinedLabel { span }
+            | Self::DuplicateLabel { span }
+            | Self::InvalidContinueLabel { span }
+            | Self::NewTargetOutsideFunction { span }
+            | Self::DuplicateParameter { span }
+            | Self::DeleteOfIdentifier { span }
+            | Self::DuplicateConstructor { span } => *span,
+        }
+    }
+
+    /// Render the error with its position and an excerpt of the offending line:
+    ///
+    /// ```text
+    /// error[E0002]: Expected `)` but found `;`
+    ///  --> 1:7
+    ///   |
+    /// 1 | if (a ;
+    ///   |       ^
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let span = self.span();
+        let start = span.start.min(source.len());
+        let (line, column) = line_column(source, start);
+        let is_line_terminator = |c| matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}');
+        let line_start = source[..start]
+            .char_indices()
+            .rfind(|(_, c)| is_line_terminator(*c))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let text = source[line_start..]
+            .split(is_line_terminator)
+            .next()
+            .unwrap_or_default();
+
+        // Underline the span, at least one character wide and never past the end of the line
+        let end = span.end.clamp(start, line_start + text.len());
+        let width = source
+            .get(start..end)
+            .map_or(0, |s| s.chars().count())
+            .max(1);
+
+        let gutter = " ".repeat(line.to_string().len());
+        format!(
+            "error[{}]: {}\n{gutter}--> {line}:{column}\n{gutter} |\n{line} | {text}\n{gutter} | {}{}\n",
+            self.code(),
+            self,
+            " ".repeat(column - 1),
+            "^".repeat(width),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken { found, .. } => match found {
+                Type::EOF => write!(f, "Unexpected end of input"),
+                found => write!(f, "Unexpected {}", describe(*found)),
+            },
+            Self::ExpectedToken {
+                expected, found, ..
+            } => write!(
+                f,
+                "Expected {} but found {}",
+                describe(*expected),
+                describe(*found)
+            ),
+            Self::MissingSemicolon { .. } => write!(
+                f,
+                "Expected a semicolon or an implicit semicolon after a statement, but found none"
+            ),
+            Self::InvalidAssignmentTarget { .. } => write!(f, "Invalid assignment target"),
+            Self::InvalidNumber { .. } => write!(f, "Invalid number"),
+            Self::EmptyParenthesizedExpression { .. } => {
+                write!(
+                    f,
+                    "Parenthesized expression must contain at least one expression"
+                )
+            }
+            Self::Lex { kind, .. } => write!(f, "{kind}"),
+            Self::NewlineAfterThrow { .. } => write!(f, "Illegal newline after throw"),
+            Self::InvalidArrowParameters { .. } => write!(f, "Invalid arrow function parameters"),
+            Self::ModuleSyntaxOutsideModule { .. } => {
+                write!(
+                    f,
+                    "Cannot use import, export or import.meta outside a module"
+                )
+            }
+            Self::ModuleDeclarationNotTopLevel { .. } => {
+                write!(
+                    f,
+                    "Import and export declarations may only appear at the top level"
+                )
+            }
+            Self::InvalidForInOfDeclaration { .. } => write!(
+                f,
+                "Only a single variable without an initializer may be declared in a for-in or for-of loop"
+            ),
+            Self::MissingDestructuringInitializer { .. } => {
+                write!(f, "Missing initializer in destructuring declaration")
+            }
+            Self::InvalidTemplateEscape { .. } => {
+                write!(f, "Invalid escape sequence in template literal")
+            }
+            Self::InvalidRegExpFlag { .. } => write!(f, "Invalid regular expression flag"),
+            Self::MixedCoalesce { .. } => write!(
+                f,
+                "Cannot mix `??` with `||` or `&&` without parentheses"
+            ),
+            Self::UnaryBeforeExponentiation { .. } => write!(
+                f,
+                "A unary operator before the left operand of `**` must be parenthesized"
+            ),
+            Self::InvalidShorthandInitializer { .. } => {
+                write!(f, "Invalid shorthand property initializer")
+            }
+            Self::UndefinedLabel { .. } => write!(f, "Undefined label"),
+            Self::DuplicateLabel { .. } => write!(f, "Label has already been declared"),
+            Self::InvalidContinueLabel { .. } => {
+                write!(f, "`continue` can only target the label of a loop")
+            }
+            Self::NewTargetOutsideFunction { .. } => {
+                write!(f, "`new.target` can only be used in functions")
+            }
+            Self::DuplicateParameter { .. } => {
+                write!(f, "Duplicate parameter name not allowed in strict mode")
+            }
+            Self::DeleteOfIdentifier { .. } => {
+                write!(f, "Cannot delete an identifier in strict mode")
+            }
+            Self::DuplicateConstructor { .. } => {
+                write!(f, "A class may only have one constructor")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(kind: Type) -> String {
+    match kind {
+        Type::EOF => "end of input".into(),
+        Type::Identifier => "identifier".into(),
+        Type::Str => "string".into(),
+        Type::NoSubstitutionTemplate
+        | Type::TemplateHead
+        | Type::TemplateMiddle
+        | Type::TemplateTail => "template literal".into(),
+        Type::RegExp => "regular expression".into(),
+        Type::Decimal | Type::Hex => "number".into(),
+        Type::LexerError => "invalid token".into(),
+        kind => format!("`{}`", kind.to_str()),
+    }
+}
+
+/// The 1-based line and column (in characters) of a byte offset in `source`
+pub fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    let mut chars = source
+        .char_indices()
+        .take_while(|(i, _)| *i < offset)
+        .peekable();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\r' if matches!(chars.peek(), Some((_, '\n'))) => {}
+            '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                line += 1;
+                column = 1;
+            }
+            _ => column += 1,
+        }
+    }
+    (line, column)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_column_test() {
+        let source = "a;\r\nbc;\nd";
+        assert_eq!(line_column(source, 0), (1, 1));
+        assert_eq!(line_column(source, 5), (2, 2));
+        assert_eq!(line_column(source, 8), (3, 1));
+    }
+
+    #[test]
+    fn render_test() {
+        let source = "a;\nif (a ;\n";
+        let error = ParseError::ExpectedToken {
+            span: Span::new(9, 10),
+            expected: Type::RParen,
+            found: Type::Semicolon,
+        };
+        assert_eq!(
+            error.render(source),
+            "error[E0002]: Expected `)` but found `;`\n --> 2:7\n  |\n2 | if (a ;\n  |       ^\n"
+        );
+    }
+}
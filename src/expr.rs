@@ -1,12 +1,12 @@
 use op::*;
 use syntax::lex::TokenTypeUtil;
+use syntax::operator::{AssignmentOperator, LogicalOperator, UnaryOperator};
 use syntax::precedence::Precedence;
 
 use super::*;
 use ast::*;
 
 #[allow(dead_code)]
-
 impl<'a> Parser<'a> {
     pub(crate) fn parse_expr(&mut self) -> Result<Expression<'a>> {
         let span = self.start_span();
@@ -46,8 +46,46 @@ impl<'a> Parser<'a> {
     }
 
     pub(crate) fn parse_assignment_expression_or_higher(&mut self) -> Result<Expression<'a>> {
+        if self.at(Type::Yield) && self.ctx.has_yield() {
+            return self.parse_yield_expression();
+        }
         let span = self.start_span();
-        let lhs = self.parse_binary_expression_or_higher(Precedence::Comma)?;
+        // `async x => x`, [no LineTerminator here] after `async`
+        if self.at(Type::Async)
+            && self.peek_kind() == Type::Identifier
+            && !self.peek_is_on_new_line()
+        {
+            self.bump_any(); // bump `async`
+            let cover = self.parse_identifier_expression()?;
+            if !self.at(Type::Arrow) {
+                return Err(ParseError::ExpectedToken {
+                    span: self.cur_token_span(),
+                    expected: Type::Arrow,
+                    found: self.cur_kind(),
+                });
+            }
+            // [no LineTerminator here] before `=>`
+            if self.cur_token().is_on_new_line {
+                return Err(self.unexpected());
+            }
+            return self.parse_arrow_function_expression(span, cover, true);
+        }
+        // `async (x) => x` starts out as a call of a function named `async`
+        let is_async_call =
+            self.at(Type::Async) && self.peek_kind() == Type::LParen && !self.peek_is_on_new_line();
+        let lhs = self.parse_conditional_expression()?;
+        // [no LineTerminator here] before `=>`
+        if self.at(Type::Arrow) && !self.cur_token().is_on_new_line {
+            return match lhs {
+                Expression::CallExpression(call)
+                    if is_async_call && matches!(call.callee, Expression::Identifier(_)) =>
+                {
+                    let cover = self.arrow_cover_from_arguments(span, call.arguments)?;
+                    self.parse_arrow_function_expression(span, cover, true)
+                }
+                lhs => self.parse_arrow_function_expression(span, lhs, false),
+            };
+        }
         let kind = self.cur_kind();
         if kind.is_assignment_operator() {
             return self.parse_assignment_expression_recursive(span, lhs);
@@ -56,21 +94,71 @@ impl<'a> Parser<'a> {
         Ok(lhs)
     }
 
+    /// Section 15.5 Generator Function Definitions
+    ///
+    /// `yield` has no argument when the next token cannot start an expression or is on a new line
+    fn parse_yield_expression(&mut self) -> Result<Expression<'a>> {
+        let span = self.start_span();
+        self.bump_any(); // bump `yield`
+        let mut delegate = false;
+        let mut argument = None;
+        if !self.cur_token().is_on_new_line {
+            delegate = self.eat(Type::Star);
+            let has_argument = !matches!(
+                self.cur_kind(),
+                Type::RParen
+                    | Type::RBrack
+                    | Type::RCurly
+                    | Type::Comma
+                    | Type::Semicolon
+                    | Type::Colon
+                    | Type::EOF
+                    | Type::TemplateMiddle
+                    | Type::TemplateTail
+            );
+            if delegate || has_argument {
+                argument = Some(self.parse_assignment_expression_or_higher()?);
+            }
+        }
+        Ok(Expression::YieldExpression(Box::new(YieldExpression {
+            span: self.end_span(span),
+            delegate,
+            argument,
+        })))
+    }
+
+    /// Section 13.14 Conditional Expression
+    fn parse_conditional_expression(&mut self) -> Result<Expression<'a>> {
+        let span = self.start_span();
+        let lhs = self.parse_binary_expression_or_higher(Precedence::Comma)?;
+        if !self.eat(Type::Question) {
+            return Ok(lhs);
+        }
+        let consequent = self.context(Context::In, Context::empty(), |p| {
+            p.parse_assignment_expression_or_higher()
+        })?;
+        self.expect(Type::Colon)?;
+        let alternate = self.parse_assignment_expression_or_higher()?;
+        Ok(Expression::ConditionalExpression(Box::new(
+            ConditionalExpression {
+                span: self.end_span(span),
+                test: lhs,
+                consequent,
+                alternate,
+            },
+        )))
+    }
+
     fn parse_assignment_expression_recursive(
         &mut self,
         span: Span,
         lhs: Expression<'a>,
     ) -> Result<Expression<'a>> {
         let operator = map_assignment_operator(self.cur_kind());
-        let left = match lhs {
-            Expression::Identifier(ident) => AssignmentTarget::Identifier(ident),
-            Expression::StaticMemberExpression(member) => {
-                AssignmentTarget::StaticMemberExpression(member)
-            }
-            Expression::ComputedMemberExpression(member) => {
-                AssignmentTarget::ComputedMemberExpression(member)
-            }
-            _ => return Err(format!("Invalid assignment target: {:?}", lhs)),
+        // Only `=` destructures, `[a] += 1` is an error
+        let left = match operator {
+            AssignmentOperator::Assign => self.reinterpret_as_assignment_target(span, lhs)?,
+            _ => self.reinterpret_as_simple_assignment_target(span, lhs)?,
         };
         self.bump_any();
         let right = self.parse_assignment_expression_or_higher()?;
@@ -84,11 +172,165 @@ impl<'a> Parser<'a> {
         )))
     }
 
+    /// Reinterpret `expr`, which started at `span`, as the target of `=` or a for-in/of loop,
+    /// where array and object literals are destructuring patterns
+    pub(crate) fn reinterpret_as_assignment_target(
+        &mut self,
+        span: Span,
+        expr: Expression<'a>,
+    ) -> Result<AssignmentTarget<'a>> {
+        match expr {
+            Expression::ArrayExpression(array) => {
+                let mut elements = vec![];
+                let mut rest = None;
+                let mut iter = array.elements.into_iter().peekable();
+                while let Some(element) = iter.next() {
+                    match element {
+                        ArrayExpressionElement::Elision(_) => elements.push(None),
+                        ArrayExpressionElement::Expression(expr) => elements.push(Some(
+                            self.reinterpret_as_assignment_target_maybe_default(span, expr)?,
+                        )),
+                        // Only the last element can collect the rest, and it has no default
+                        ArrayExpressionElement::SpreadElement(spread) if iter.peek().is_none() => {
+                            rest = Some(Box::new(AssignmentTargetRest {
+                                span: spread.span,
+                                target: self
+                                    .reinterpret_as_assignment_target(span, spread.argument)?,
+                            }));
+                        }
+                        ArrayExpressionElement::SpreadElement(_) => {
+                            return Err(ParseError::InvalidAssignmentTarget {
+                                span: self.end_span(span),
+                            })
+                        }
+                    }
+                }
+                // `[...a,] = b`
+                if let Some(span) = array.trailing_comma.filter(|_| rest.is_some()) {
+                    return Err(ParseError::InvalidAssignmentTarget { span });
+                }
+                Ok(AssignmentTarget::ArrayAssignmentTarget(Box::new(
+                    ArrayAssignmentTarget {
+                        span: array.span,
+                        elements,
+                        rest,
+                    },
+                )))
+            }
+            Expression::ObjectExpression(object) => {
+                // The `a = 1` shorthand properties are defaults now
+                self.cover_initialized_names
+                    .retain(|cover| cover.start < object.span.start || cover.end > object.span.end);
+                let mut properties = vec![];
+                let mut rest = None;
+                let mut iter = object.properties.into_iter().peekable();
+                while let Some(property) = iter.next() {
+                    match property {
+                        ObjectPropertyKind::ObjectProperty(property)
+                            if property.kind == PropertyKind::Init =>
+                        {
+                            properties.push(AssignmentTargetProperty {
+                                span: property.span,
+                                key: property.key,
+                                value: self.reinterpret_as_assignment_target_maybe_default(
+                                    span,
+                                    property.value,
+                                )?,
+                            });
+                        }
+                        // Only the last property can collect the rest, into a simple target
+                        ObjectPropertyKind::SpreadProperty(spread) if iter.peek().is_none() => {
+                            rest = Some(Box::new(AssignmentTargetRest {
+                                span: spread.span,
+                                target: self.reinterpret_as_simple_assignment_target(
+                                    span,
+                                    spread.argument,
+                                )?,
+                            }));
+                        }
+                        // Methods, accessors and other spreads
+                        _ => {
+                            return Err(ParseError::InvalidAssignmentTarget {
+                                span: self.end_span(span),
+                            })
+                        }
+                    }
+                }
+                // `({...a,} = b)`
+                if let Some(span) = object.trailing_comma.filter(|_| rest.is_some()) {
+                    return Err(ParseError::InvalidAssignmentTarget { span });
+                }
+                Ok(AssignmentTarget::ObjectAssignmentTarget(Box::new(
+                    ObjectAssignmentTarget {
+                        span: object.span,
+                        properties,
+                        rest,
+                    },
+                )))
+            }
+            // `([a]) = 1` is an error, parentheses are only unwrapped around simple targets
+            expr => self.reinterpret_as_simple_assignment_target(span, expr),
+        }
+    }
+
+    /// An element of a destructuring pattern, where `a = 1` gives `a` a default value.
+    /// Errors are reported for the whole pattern, which started at `span`.
+    fn reinterpret_as_assignment_target_maybe_default(
+        &mut self,
+        span: Span,
+        expr: Expression<'a>,
+    ) -> Result<AssignmentTargetMaybeDefault<'a>> {
+        match expr {
+            // The left side has already been reinterpreted
+            Expression::AssignmentExpression(assign)
+                if assign.operator == AssignmentOperator::Assign =>
+            {
+                Ok(AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(
+                    Box::new(AssignmentTargetWithDefault {
+                        span: assign.span,
+                        binding: assign.left,
+                        init: assign.right,
+                    }),
+                ))
+            }
+            expr => self
+                .reinterpret_as_assignment_target(span, expr)
+                .map(AssignmentTargetMaybeDefault::AssignmentTarget),
+        }
+    }
+
+    /// Reinterpret `expr`, which started at `span`, as the target of an update or a compound
+    /// assignment
+    pub(crate) fn reinterpret_as_simple_assignment_target(
+        &mut self,
+        span: Span,
+        expr: Expression<'a>,
+    ) -> Result<AssignmentTarget<'a>> {
+        match expr {
+            // `(a)++` and `(a.b) = 1`
+            Expression::ParenthesizedExpression(paren) => {
+                self.reinterpret_as_simple_assignment_target(span, paren.expression)
+            }
+            Expression::Identifier(ident) => Ok(AssignmentTarget::Identifier(ident)),
+            Expression::StaticMemberExpression(member) => {
+                Ok(AssignmentTarget::StaticMemberExpression(member))
+            }
+            Expression::ComputedMemberExpression(member) => {
+                Ok(AssignmentTarget::ComputedMemberExpression(member))
+            }
+            _ => Err(ParseError::InvalidAssignmentTarget {
+                span: self.end_span(span),
+            }),
+        }
+    }
+
     pub(crate) fn parse_unary_expression_or_higher(
         &mut self,
         lhs_span: Span,
     ) -> Result<Expression<'a>> {
-        let is_update_expression = !matches!(self.cur_kind(), kind if kind.is_unary_operator());
+        let is_await = self.at(Type::Await) && self.ctx.has_await();
+        let is_update_expression =
+            !is_await && !matches!(self.cur_kind(), kind if kind.is_unary_operator());
 
         if is_update_expression {
             return self.parse_update_expression(lhs_span);
@@ -102,27 +344,43 @@ impl<'a> Parser<'a> {
     ) -> Result<Expression<'a>> {
         match self.cur_kind() {
             kind if kind.is_unary_operator() => self.parse_unary_expression(),
+            Type::Await if self.ctx.has_await() => self.parse_await_expression(),
             _ => self.parse_update_expression(lhs_span),
         }
     }
 
     /// Section 13.4 Update Expression
-    #[allow(unused_variables)]
     fn parse_update_expression(&mut self, lhs_span: Span) -> Result<Expression<'a>> {
-        // TODO: support prefix update expressions
-
-        // let kind = self.cur_kind();
-        // // ++ -- prefix update expressions
-        // if kind.is_update_operator() {
-        //     ...
-        // }
+        let kind = self.cur_kind();
+        // ++ -- prefix update expressions
+        if kind.is_update_operator() {
+            let operator = map_update_operator(kind);
+            self.bump_any();
+            let argument_span = self.start_span();
+            let argument = self.parse_simple_unary_expression(argument_span)?;
+            let argument = self.reinterpret_as_simple_assignment_target(argument_span, argument)?;
+            return Ok(Expression::UpdateExpression(Box::new(UpdateExpression {
+                span: self.end_span(lhs_span),
+                operator,
+                prefix: true,
+                argument,
+            })));
+        }
 
-        // let span = self.start_span();
         let lhs = self.parse_lhs_expression_or_higher()?;
         // ++ -- postfix update expressions
-        // if self.cur_kind().is_update_operator() && !self.cur_token().is_on_new_line {
-        //     ...
-        // }
+        // [no LineTerminator here], otherwise the operator starts the next statement
+        if self.cur_kind().is_update_operator() && !self.cur_token().is_on_new_line {
+            let argument = self.reinterpret_as_simple_assignment_target(lhs_span, lhs)?;
+            let operator = map_update_operator(self.cur_kind());
+            self.bump_any();
+            return Ok(Expression::UpdateExpression(Box::new(UpdateExpression {
+                span: self.end_span(lhs_span),
+                operator,
+                prefix: false,
+                argument,
+            })));
+        }
         Ok(lhs)
     }
 
@@ -134,22 +392,46 @@ impl<'a> Parser<'a> {
         Ok(lhs)
     }
 
-    /// Section 13.3 Call Expression
+    /// Section 13.3 Call Expression and 13.3.9 Optional Chains
+    ///
+    /// Everything after the first `?.` belongs to the chain, which is wrapped in a
+    /// `ChainExpression` so that `a?.b.c` short-circuits as a whole.
     fn parse_call_expression_rest(
         &mut self,
         lhs_span: Span,
         lhs: Expression<'a>,
     ) -> Result<Expression<'a>> {
         let mut lhs = lhs;
+        let mut in_optional_chain = false;
         loop {
-            lhs = self.parse_member_expression_rest(lhs_span, lhs)?;
+            lhs = self.parse_member_expression_rest(lhs_span, lhs, in_optional_chain)?;
 
+            if self.at(Type::QuestionDot) {
+                in_optional_chain = true;
+                self.bump_any(); // advance `?.`
+                lhs = match self.cur_kind() {
+                    Type::LParen => self.parse_call_arguments(lhs_span, lhs, true)?,
+                    Type::LBrack => self.parse_computed_member_expression(lhs_span, lhs, true)?,
+                    Type::NoSubstitutionTemplate | Type::TemplateHead => {
+                        return Err(self.unexpected())
+                    }
+                    // `a?.b` without the `.`
+                    _ => self.parse_static_member_expression(lhs_span, lhs, true)?,
+                };
+                continue;
+            }
             if self.at(Type::LParen) {
-                lhs = self.parse_call_arguments(lhs_span, lhs)?;
+                lhs = self.parse_call_arguments(lhs_span, lhs, false)?;
                 continue;
             }
             break;
         }
+        if in_optional_chain {
+            lhs = Expression::ChainExpression(Box::new(ChainExpression {
+                span: self.end_span(lhs_span),
+                expression: lhs,
+            }));
+        }
         Ok(lhs)
     }
 
@@ -157,61 +439,135 @@ impl<'a> Parser<'a> {
         &mut self,
         lhs_span: Span,
         callee: Expression<'a>,
+        optional: bool,
     ) -> Result<Expression<'a>> {
+        let arguments = self.parse_arguments()?;
+        Ok(Expression::CallExpression(Box::new(CallExpression {
+            span: self.end_span(lhs_span),
+            callee,
+            arguments,
+            optional,
+        })))
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<Argument<'a>>> {
         self.expect(Type::LParen)?;
         let mut arguments = Vec::new();
         while !self.at(Type::RParen) {
-            let argument = self.parse_assignment_expression_or_higher()?;
+            let argument = if self.at(Type::Dot3) {
+                Argument::SpreadElement(Box::new(self.parse_spread_element()?))
+            } else {
+                Argument::Expression(self.context(Context::In, Context::empty(), |p| {
+                    p.parse_assignment_expression_or_higher()
+                })?)
+            };
             arguments.push(argument);
-            if self.at(Type::Comma) {
-                self.bump_any();
+            if !self.at(Type::RParen) {
+                self.expect(Type::Comma)?;
             }
         }
         self.expect(Type::RParen)?;
-        Ok(Expression::CallExpression(Box::new(CallExpression {
-            span: self.end_span(lhs_span),
-            callee,
-            arguments,
-        })))
+        Ok(arguments)
     }
 
     /// Section 13.3 Member Expression
     fn parse_member_expression_or_higher(&mut self) -> Result<Expression<'a>> {
         let span = self.start_span();
-        let lhs = self.parse_primary_expression()?;
-        self.parse_member_expression_rest(span, lhs)
+        let lhs = if self.at(Type::New) {
+            self.parse_new_expression()?
+        } else {
+            self.parse_primary_expression()?
+        };
+        self.parse_member_expression_rest(span, lhs, false)
+    }
+
+    /// Section 13.3 `new` MemberExpression Arguments, `new` NewExpression and `new.target`
+    fn parse_new_expression(&mut self) -> Result<Expression<'a>> {
+        let span = self.start_span();
+        let meta = self.parse_identifier_name()?; // advance `new`
+        if self.eat(Type::Dot) {
+            self.expect_peek_only(Type::Target)?;
+            let property = self.parse_identifier_name()?;
+            if !self.ctx.has_function() {
+                return Err(ParseError::NewTargetOutsideFunction {
+                    span: self.end_span(span),
+                });
+            }
+            return Ok(Expression::MetaProperty(Box::new(MetaProperty {
+                span: self.end_span(span),
+                meta,
+                property,
+            })));
+        }
+
+        let callee_span = self.start_span();
+        let callee = if self.at(Type::New) {
+            self.parse_new_expression()?
+        } else {
+            self.parse_primary_expression()?
+        };
+        // the arguments belong to the innermost `new`, calls are not part of the callee
+        let callee = self.parse_member_expression_rest(callee_span, callee, false)?;
+        // `new a?.b()` is an early error
+        if self.at(Type::QuestionDot) {
+            return Err(self.unexpected());
+        }
+        let arguments = if self.at(Type::LParen) {
+            self.parse_arguments()?
+        } else {
+            vec![]
+        };
+        Ok(Expression::NewExpression(Box::new(NewExpression {
+            span: self.end_span(span),
+            callee,
+            arguments,
+        })))
     }
 
     /// parse rhs of a member expression, starting from lhs
+    ///
+    /// A template cannot tag an optional chain, ``a?.b`c` `` is an early error.
     #[allow(unused_variables)]
     fn parse_member_expression_rest(
         &mut self,
         lhs_span: Span,
         lhs: Expression<'a>,
+        in_optional_chain: bool,
     ) -> Result<Expression<'a>> {
         let mut lhs = lhs;
         loop {
             lhs = match self.cur_kind() {
-                Type::Dot => self.parse_static_member_expression(lhs_span, lhs)?,
-                Type::LBrack => self.parse_computed_member_expression(lhs_span, lhs)?,
+                Type::Dot => {
+                    self.bump_any(); // advance `.`
+                    self.parse_static_member_expression(lhs_span, lhs, false)?
+                }
+                Type::LBrack => self.parse_computed_member_expression(lhs_span, lhs, false)?,
+                Type::NoSubstitutionTemplate | Type::TemplateHead if in_optional_chain => {
+                    return Err(self.unexpected())
+                }
+                Type::NoSubstitutionTemplate | Type::TemplateHead => {
+                    self.parse_tagged_template(lhs_span, lhs)?
+                }
                 _ => break,
             };
         }
         Ok(lhs)
     }
 
+    /// The property after `.` or `?.`, which the caller has advanced
     fn parse_static_member_expression(
         &mut self,
         lhs_span: Span,
         lhs: Expression<'a>,
+        optional: bool,
     ) -> Result<Expression<'a>> {
-        self.bump_any(); // advance `.`
         let ident = self.parse_identifier_name()?;
         Ok(Expression::StaticMemberExpression(Box::new(
             StaticMemberExpression {
                 span: self.end_span(lhs_span),
                 object: lhs,
                 property: ident,
+                optional,
             },
         )))
     }
@@ -220,15 +576,32 @@ impl<'a> Parser<'a> {
         &mut self,
         lhs_span: Span,
         lhs: Expression<'a>,
+        optional: bool,
     ) -> Result<Expression<'a>> {
         self.bump_any(); // advance `[`
-        let property = self.parse_expr()?;
+        let property = self.context(Context::In, Context::empty(), Self::parse_expr)?;
         self.expect(Type::RBrack)?;
         Ok(Expression::ComputedMemberExpression(Box::new(
             ComputedMemberExpression {
                 span: self.end_span(lhs_span),
                 object: lhs,
                 expression: property,
+                optional,
+            },
+        )))
+    }
+
+    fn parse_tagged_template(
+        &mut self,
+        lhs_span: Span,
+        lhs: Expression<'a>,
+    ) -> Result<Expression<'a>> {
+        let quasi = self.parse_template_literal(true)?;
+        Ok(Expression::TaggedTemplateExpression(Box::new(
+            TaggedTemplateExpression {
+                span: self.end_span(lhs_span),
+                tag: lhs,
+                quasi,
             },
         )))
     }
@@ -239,10 +612,38 @@ impl<'a> Parser<'a> {
 
         match self.cur_kind() {
             Type::Identifier => self.parse_identifier_expression(),
+            Type::This => {
+                self.bump_any();
+                Ok(Expression::ThisExpression(Box::new(ThisExpression {
+                    span: self.end_span(span),
+                })))
+            }
             kind if kind.is_literal() => self.parse_literal_expression(),
+            Type::Slash | Type::SlashEq => self
+                .parse_literal_regexp()
+                .map(|literal| Expression::RegExpLiteral(Box::new(literal))),
+            Type::NoSubstitutionTemplate | Type::TemplateHead => self
+                .parse_template_literal(false)
+                .map(|template| Expression::TemplateLiteral(Box::new(template))),
             Type::LBrack => self.parse_array_expression(),
             Type::LCurly => self.parse_object_expression(),
             Type::LParen => self.parse_parenthesized_expression(span),
+            Type::Function => self.parse_function_expression(),
+            Type::Async if self.peek_kind() == Type::Function && !self.peek_is_on_new_line() => {
+                self.parse_function_expression()
+            }
+            Type::Class => self.parse_class_expression(),
+            Type::Import => self.parse_import_expression(),
+            Type::Super => {
+                self.bump_any();
+                // `super` can only be called or have a property accessed
+                if !matches!(self.cur_kind(), Type::LParen | Type::Dot | Type::LBrack) {
+                    return Err(self.unexpected());
+                }
+                Ok(Expression::Super(Box::new(Super {
+                    span: self.end_span(span),
+                })))
+            }
             _ => self.parse_identifier_expression(),
         }
     }
@@ -251,10 +652,37 @@ impl<'a> Parser<'a> {
         self.expect(Type::LParen)?;
         let mut expressions = vec![];
         while !self.at(Type::RParen) {
-            let expression = self.parse_assignment_expression_or_higher()?;
+            if self.at(Type::Dot3) {
+                // `(a, ...rest)` only covers the parameters of an arrow function
+                let rest_span = self.start_span();
+                self.bump_any(); // bump `...`
+                let argument = self.parse_binding_pattern()?;
+                let rest = RestElement {
+                    span: self.end_span(rest_span),
+                    argument,
+                };
+                self.expect(Type::RParen)?;
+                if !self.at(Type::Arrow) {
+                    return Err(ParseError::ExpectedToken {
+                        span: self.cur_token_span(),
+                        expected: Type::Arrow,
+                        found: self.cur_kind(),
+                    });
+                }
+                self.cover_rest = Some(Box::new(rest));
+                return Ok(Expression::SequenceExpression(Box::new(
+                    SequenceExpression {
+                        span: self.end_span(span),
+                        expressions,
+                    },
+                )));
+            }
+            let expression = self.context(Context::In, Context::empty(), |p| {
+                p.parse_assignment_expression_or_higher()
+            })?;
             expressions.push(expression);
-            if self.at(Type::Comma) {
-                self.bump_any();
+            if !self.at(Type::RParen) {
+                self.expect(Type::Comma)?;
             }
         }
         self.expect(Type::RParen)?;
@@ -262,7 +690,16 @@ impl<'a> Parser<'a> {
         let paren_span = self.end_span(span);
 
         if expressions.is_empty() {
-            return Err("Parenthesized expression must contain at least one expression".into());
+            // `()` only covers the parameters of an arrow function
+            if self.at(Type::Arrow) {
+                return Ok(Expression::SequenceExpression(Box::new(
+                    SequenceExpression {
+                        span: paren_span,
+                        expressions,
+                    },
+                )));
+            }
+            return Err(ParseError::EmptyParenthesizedExpression { span: paren_span });
         }
 
         // ParenthesizedExpression is from acorn --preserveParens
@@ -289,6 +726,7 @@ impl<'a> Parser<'a> {
 
         self.expect(Type::LBrack)?;
         let mut elements = Vec::new();
+        let mut trailing_comma = None;
 
         while !self.at(Type::RBrack) {
             let span = self.start_span();
@@ -297,11 +735,12 @@ impl<'a> Parser<'a> {
                 continue;
             }
 
-            let element = self.parse_assignment_expression_or_higher()?;
-            elements.push(ArrayExpressionElement::Expression(element));
+            elements.push(self.parse_array_expression_element()?);
 
-            if self.at(Type::Comma) {
-                self.bump_any();
+            if !self.at(Type::RBrack) {
+                let comma = self.cur_token_span();
+                self.expect(Type::Comma)?;
+                trailing_comma = self.at(Type::RBrack).then_some(comma);
             }
         }
 
@@ -310,6 +749,7 @@ impl<'a> Parser<'a> {
         Ok(Expression::ArrayExpression(Box::new(ArrayExpression {
             span: self.end_span(span),
             elements,
+            trailing_comma,
         })))
     }
 
@@ -318,12 +758,30 @@ impl<'a> Parser<'a> {
             Type::Comma => Ok(ArrayExpressionElement::Elision(Elision {
                 span: self.start_span(),
             })),
+            Type::Dot3 => Ok(ArrayExpressionElement::SpreadElement(Box::new(
+                self.parse_spread_element()?,
+            ))),
             _ => self
-                .parse_assignment_expression_or_higher()
+                .context(Context::In, Context::empty(), |p| {
+                    p.parse_assignment_expression_or_higher()
+                })
                 .map(ArrayExpressionElement::Expression),
         }
     }
 
+    /// `...iterable`
+    pub(crate) fn parse_spread_element(&mut self) -> Result<SpreadElement<'a>> {
+        let span = self.start_span();
+        self.bump_any(); // bump `...`
+        let argument = self.context(Context::In, Context::empty(), |p| {
+            p.parse_assignment_expression_or_higher()
+        })?;
+        Ok(SpreadElement {
+            span: self.end_span(span),
+            argument,
+        })
+    }
+
     pub(crate) fn parse_literal_expression(&mut self) -> Result<Expression<'a>> {
         match self.cur_kind() {
             Type::Str => self
@@ -353,6 +811,85 @@ impl<'a> Parser<'a> {
         Ok(StringLiteral { span, value })
     }
 
+    /// Section 13.2.7.3 Regular Expression Literals
+    fn parse_literal_regexp(&mut self) -> Result<RegExpLiteral<'a>> {
+        self.relex_regex();
+        if !self.at(Type::RegExp) {
+            return Err(self.unexpected());
+        }
+        let span = self.start_span();
+        let raw = self.cur_string();
+        let (pattern, flags) = raw[1..].rsplit_once('/').unwrap_or_default();
+        let flags_start = span.start + raw.len() - flags.len();
+        for (i, flag) in flags.char_indices() {
+            let seen = &flags[..i];
+            let repeated =
+                seen.contains(flag) || (matches!(flag, 'u' | 'v') && seen.contains(['u', 'v']));
+            if !"dgimsuvy".contains(flag) || repeated {
+                let start = flags_start + i;
+                return Err(ParseError::InvalidRegExpFlag {
+                    span: Span::new(start, start + flag.len_utf8()),
+                });
+            }
+        }
+        self.bump_any();
+        Ok(RegExpLiteral {
+            span: self.end_span(span),
+            pattern,
+            flags,
+        })
+    }
+
+    /// Section 13.2.8 Template Literals
+    ///
+    /// Malformed escapes are only allowed in a `tagged` template, and leave the quasi uncooked.
+    fn parse_template_literal(&mut self, tagged: bool) -> Result<TemplateLiteral<'a>> {
+        let span = self.start_span();
+        let mut quasis = vec![];
+        let mut expressions = vec![];
+        loop {
+            let quasi = self.parse_template_element(tagged)?;
+            let tail = quasi.tail;
+            quasis.push(quasi);
+            if tail {
+                break;
+            }
+            let expression = self.context(Context::In, Context::empty(), Self::parse_expr)?;
+            expressions.push(expression);
+            // the lexer turns the `}` closing the substitution into the rest of the template
+            if !matches!(self.cur_kind(), Type::TemplateMiddle | Type::TemplateTail) {
+                return Err(self.unexpected());
+            }
+        }
+        Ok(TemplateLiteral {
+            span: self.end_span(span),
+            quasis,
+            expressions,
+        })
+    }
+
+    fn parse_template_element(&mut self, tagged: bool) -> Result<TemplateElement<'a>> {
+        let tail = matches!(
+            self.cur_kind(),
+            Type::NoSubstitutionTemplate | Type::TemplateTail
+        );
+        // the text is between `` ` `` or `}`, and `` ` `` or `${`
+        let token = self.cur_string();
+        let raw = &token[1..token.len() - if tail { 1 } else { 2 }];
+        let start = self.cur_token_span().start + 1;
+        let span = Span::new(start, start + raw.len());
+        let cooked = cook_template(raw);
+        if cooked.is_none() && !tagged {
+            return Err(ParseError::InvalidTemplateEscape { span });
+        }
+        self.bump_any();
+        Ok(TemplateElement {
+            span,
+            tail,
+            value: TemplateElementValue { raw, cooked },
+        })
+    }
+
     pub(crate) fn parse_literal_boolean(&mut self) -> Result<BooleanLiteral> {
         let span = self.start_span();
         let value = match self.cur_kind() {
@@ -377,13 +914,11 @@ impl<'a> Parser<'a> {
         }
 
         // TODO: implement our own number parser
-        use std::num::ParseFloatError;
-
         let raw = self.cur_string();
-        let value = raw
-            .parse::<f64>()
-            .map_err(|err: ParseFloatError| format!("Failed to parse number: {:?}", err))?;
         let span = self.start_span();
+        let value = raw.parse::<f64>().map_err(|_| ParseError::InvalidNumber {
+            span: self.cur_token_span(),
+        })?;
         self.bump_any();
         Ok(NumericLiteral { span, value, raw })
     }
@@ -393,6 +928,11 @@ impl<'a> Parser<'a> {
         let operator = map_unary_operator(self.cur_kind());
         self.bump_any();
         let argument = self.parse_simple_unary_expression(span)?;
+        if operator == UnaryOperator::Delete && self.is_strict() && is_identifier(&argument) {
+            return Err(ParseError::DeleteOfIdentifier {
+                span: self.end_span(span),
+            });
+        }
         Ok(Expression::UnaryExpression(Box::new(UnaryExpression {
             span: self.end_span(span),
             operator,
@@ -400,6 +940,19 @@ impl<'a> Parser<'a> {
         })))
     }
 
+    /// Section 15.8 Async Function Definitions
+    fn parse_await_expression(&mut self) -> Result<Expression<'a>> {
+        let span = self.start_span();
+        self.bump_any(); // bump `await`
+        let argument = self.parse_simple_unary_expression(self.start_span())?;
+        let span = self.end_span(span);
+        self.last_await = Some(span);
+        Ok(Expression::AwaitExpression(Box::new(AwaitExpression {
+            span,
+            argument,
+        })))
+    }
+
     pub(crate) fn parse_binary_expression_or_higher(
         &mut self,
         lhs_precedence: Precedence,
@@ -437,22 +990,45 @@ impl<'a> Parser<'a> {
                 break;
             }
 
-            // TODO:
             // Omit the In keyword for the grammar in 13.10 Relational Operators
             // RelationalExpression[In, Yield, Await] :
             // [+In] RelationalExpression[+In, ?Yield, ?Await] in ShiftExpression[?Yield, ?Await]
-            // if kind == Type::In && !self.ctx.has_in() {
-            //     break;
-            // }
+            if kind == Type::In && !self.ctx.has_in() {
+                break;
+            }
+
+            // `-a ** b` is ambiguous, the unary operand has to be parenthesized
+            if kind == Type::Star2
+                && matches!(
+                    lhs,
+                    Expression::UnaryExpression(_) | Expression::AwaitExpression(_)
+                )
+            {
+                return Err(ParseError::UnaryBeforeExponentiation {
+                    span: self.end_span(lhs_span),
+                });
+            }
 
             self.bump_any(); // bump operator
             let rhs = self.parse_binary_expression_or_higher(left_precedence)?;
 
             lhs = if kind.is_logical_operator() {
+                let operator = map_logical_operator(kind);
+                // `a ?? b || c` is an early error, one side has to be parenthesized
+                let is_mixed = |operand: &Expression| {
+                    matches!(operand, Expression::LogicalExpression(logical)
+                        if (logical.operator == LogicalOperator::Coalesce)
+                            != (operator == LogicalOperator::Coalesce))
+                };
+                if is_mixed(&lhs) || is_mixed(&rhs) {
+                    return Err(ParseError::MixedCoalesce {
+                        span: self.end_span(lhs_span),
+                    });
+                }
                 Expression::LogicalExpression(Box::new(LogicalExpression {
                     span: self.end_span(lhs_span),
                     left: lhs,
-                    operator: map_logical_operator(kind),
+                    operator,
                     right: rhs,
                 }))
             } else if kind.is_binary_operator() {
@@ -481,14 +1057,31 @@ impl<'a> Parser<'a> {
     }
 
     pub(crate) fn parse_identifier(&mut self) -> Result<Identifier<'a>> {
-        if !matches!(self.cur_kind(), Type::Identifier) {
+        if !self.at_identifier() {
             return Err(self.unexpected());
         }
+        let is_await = self.at(Type::Await);
         let (span, name) = self.parse_identifier_kind();
+        if is_await {
+            self.last_await = Some(span);
+        }
         // self.check_identifier(span, &name);
         Ok(Identifier { span, name })
     }
 
+    /// Whether the current token is an identifier reference, binding identifier or label
+    pub(crate) fn at_identifier(&self) -> bool {
+        // `await` and `yield` are only keywords where [Await] or [Yield] apply, or in modules
+        let script = self.source_type == SourceType::Script;
+        match self.cur_kind() {
+            // `async` is only a keyword before a function or arrow function
+            Type::Identifier | Type::Async => true,
+            Type::Await => script && !self.ctx.has_await(),
+            Type::Yield => script && !self.ctx.has_yield(),
+            _ => false,
+        }
+    }
+
     #[inline]
     pub(crate) fn parse_identifier_kind(&mut self) -> (Span, &'a str) {
         let span = self.start_span();
@@ -498,3 +1091,97 @@ impl<'a> Parser<'a> {
         (self.end_span(span), name)
     }
 }
+
+/// The text of a template with its escapes interpreted, `None` if one is malformed
+fn cook_template(raw: &str) -> Option<String> {
+    let mut cooked = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\r' {
+            // `\r\n` and `\r` are read as `\n`
+            chars.next_if_eq(&'\n');
+            cooked.push('\n');
+            continue;
+        }
+        if c != '\\' {
+            cooked.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => cooked.push('\n'),
+            't' => cooked.push('\t'),
+            'r' => cooked.push('\r'),
+            'b' => cooked.push('\u{8}'),
+            'f' => cooked.push('\u{c}'),
+            'v' => cooked.push('\u{b}'),
+            '0' if !chars.peek().is_some_and(char::is_ascii_digit) => cooked.push('\0'),
+            '0'..='9' => return None,
+            'x' => cooked.push(char::from_u32(cook_hex(&mut chars, 2)?)?),
+            'u' => {
+                let code = cook_unicode_escape(&mut chars)?;
+                // a surrogate pair is written as two escapes, a lone surrogate is replaced
+                let low = match code {
+                    0xD800..=0xDBFF => {
+                        let mut lookahead = chars.clone();
+                        (lookahead.next() == Some('\\') && lookahead.next() == Some('u'))
+                            .then(|| cook_unicode_escape(&mut lookahead))
+                            .flatten()
+                            .filter(|low| (0xDC00..=0xDFFF).contains(low))
+                            .map(|low| (low, lookahead))
+                    }
+                    _ => None,
+                };
+                let code = match low {
+                    Some((low, lookahead)) => {
+                        chars = lookahead;
+                        0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00)
+                    }
+                    None => code,
+                };
+                cooked.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            // line continuation
+            '\r' => {
+                chars.next_if_eq(&'\n');
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => {}
+            c => cooked.push(c),
+        }
+    }
+    Some(cooked)
+}
+
+/// Whether `expr` is an identifier, possibly in parentheses
+fn is_identifier(expr: &Expression) -> bool {
+    match expr {
+        Expression::Identifier(_) => true,
+        Expression::ParenthesizedExpression(paren) => is_identifier(&paren.expression),
+        _ => false,
+    }
+}
+
+/// The code point of `\uXXXX` or `\u{X...}`, after the `u`
+fn cook_unicode_escape(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<u32> {
+    if chars.next_if_eq(&'{').is_none() {
+        return cook_hex(chars, 4);
+    }
+    let mut code = 0u32;
+    let mut digits = 0;
+    while let Some(digit) = chars.next_if(char::is_ascii_hexdigit) {
+        code = code * 16 + digit.to_digit(16)?;
+        digits += 1;
+        if code > 0x10FFFF {
+            return None;
+        }
+    }
+    chars.next_if_eq(&'}')?;
+    (digits > 0).then_some(code)
+}
+
+fn cook_hex(chars: &mut std::iter::Peekable<std::str::Chars>, len: usize) -> Option<u32> {
+    let mut code = 0;
+    for _ in 0..len {
+        code = code * 16 + chars.next()?.to_digit(16)?;
+    }
+    Some(code)
+}
@@ -8,16 +8,137 @@ impl<'a> Parser<'a> {
         &mut self,
         stmt_ctx: StatementContext,
     ) -> Result<Statement<'a>> {
-        let decl = self.parse_function_impl()?;
+        let decl = self.parse_function_impl(true)?;
 
         Ok(Statement::FunctionDeclaration(decl))
     }
 
-    pub(crate) fn parse_function_impl(&mut self) -> Result<Box<Function<'a>>> {
+    /// A function, whose name is required in declarations other than `export default`
+    pub(crate) fn parse_function_impl(&mut self, require_id: bool) -> Result<Box<Function<'a>>> {
         let span = self.start_span();
+        let is_async = self.eat(Type::Async);
         self.expect(Type::Function)?;
+        let is_generator = self.eat(Type::Star);
+        if require_id && !self.cur_kind().is_identifier() {
+            return Err(self.unexpected());
+        }
         let id = self.parse_function_id()?;
-        self.parse_function(span, id)
+        self.parse_function(span, id, is_async, is_generator)
+    }
+
+    pub(crate) fn parse_function_expression(&mut self) -> Result<Expression<'a>> {
+        self.parse_function_impl(false)
+            .map(Expression::FunctionExpression)
+    }
+
+    /// Section 15.3 Arrow Function Definitions
+    ///
+    /// `cover` is the expression parsed before `=>`: an identifier, or the contents of
+    /// `parse_parenthesized_expression`, which are reinterpreted as the parameters.
+    /// A rest parameter is left in `cover_rest`.
+    pub(crate) fn parse_arrow_function_expression(
+        &mut self,
+        span: Span,
+        cover: Expression<'a>,
+        is_async: bool,
+    ) -> Result<Expression<'a>> {
+        let params_span = self.end_span(span);
+        let covered = match cover {
+            Expression::Identifier(ident) => vec![Expression::Identifier(ident)],
+            Expression::ParenthesizedExpression(paren) => vec![paren.expression],
+            // only produced by parentheses at this point, `a, b => c` never gets here
+            Expression::SequenceExpression(seq) => seq.expressions,
+            _ => return Err(ParseError::InvalidArrowParameters { span: params_span }),
+        };
+        let params = covered
+            .into_iter()
+            .map(|param| {
+                pattern::binding_pattern_from_expression(param)
+                    .ok_or(ParseError::InvalidArrowParameters { span: params_span })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.cover_initialized_names
+            .retain(|cover| cover.start < params_span.start || cover.end > params_span.end);
+        let params = Box::new(FormalParameters {
+            span: params_span,
+            params,
+            rest: self.cover_rest.take(),
+        });
+        self.check_duplicate_parameters(&params, true)?;
+
+        self.bump_any(); // advance `=>`
+        let expression = !self.at(Type::LCurly);
+        let body = self.function_context(is_async, false, true, |p| {
+            if !expression {
+                return p.parse_function_body();
+            }
+            let body_span = p.start_span();
+            let expression = p.parse_assignment_expression_or_higher()?;
+            let span = p.end_span(body_span);
+            let stmt = ExpressionStatement { span, expression };
+            Ok(Box::new(FunctionBody {
+                span,
+                statements: vec![Statement::ExpressionStatement(Box::new(stmt))],
+            }))
+        })?;
+        Ok(Expression::ArrowFunctionExpression(Box::new(
+            ArrowFunctionExpression {
+                span: self.end_span(span),
+                expression,
+                is_async,
+                params,
+                body,
+            },
+        )))
+    }
+
+    /// The arguments of `async(...)`, which started at `span`, as the `cover` of the parameters
+    /// of an async arrow function. A spread argument must come last and becomes `cover_rest`,
+    /// and no argument may contain `await`.
+    pub(crate) fn arrow_cover_from_arguments(
+        &mut self,
+        span: Span,
+        arguments: Vec<Argument<'a>>,
+    ) -> Result<Expression<'a>> {
+        // `await` in the arguments was parsed as an identifier or an await expression of the
+        // enclosing function, either is an error in the parameters
+        if let Some(span) = self
+            .last_await
+            .filter(|await_span| await_span.start >= span.start)
+        {
+            return Err(ParseError::UnexpectedToken {
+                span,
+                found: Type::Await,
+            });
+        }
+        let mut expressions = vec![];
+        let mut iter = arguments.into_iter().peekable();
+        while let Some(argument) = iter.next() {
+            match argument {
+                Argument::Expression(expr) => expressions.push(expr),
+                Argument::SpreadElement(spread) if iter.peek().is_none() => {
+                    let argument = pattern::binding_pattern_from_expression(spread.argument)
+                        .ok_or(ParseError::InvalidArrowParameters {
+                            span: self.end_span(span),
+                        })?;
+                    self.cover_rest = Some(Box::new(RestElement {
+                        span: spread.span,
+                        argument,
+                    }));
+                }
+                Argument::SpreadElement(_) => {
+                    return Err(ParseError::InvalidArrowParameters {
+                        span: self.end_span(span),
+                    })
+                }
+            }
+        }
+        Ok(Expression::SequenceExpression(Box::new(
+            SequenceExpression {
+                span: self.end_span(span),
+                expressions,
+            },
+        )))
     }
 
     pub(crate) fn parse_function_id(&mut self) -> Result<Option<Identifier<'a>>> {
@@ -29,52 +150,146 @@ impl<'a> Parser<'a> {
         Ok(id)
     }
 
-    pub(crate) fn parse_formal_parameters(&mut self) -> Result<Box<FormalParameters<'a>>> {
+    /// `unique` parameters cannot bind a name twice, as in arrow functions and methods
+    pub(crate) fn parse_formal_parameters(
+        &mut self,
+        unique: bool,
+    ) -> Result<Box<FormalParameters<'a>>> {
         let span = self.start_span();
         self.expect(Type::LParen)?;
 
         let mut params = vec![];
+        let mut rest = None;
         while !self.at(Type::RParen) {
-            let (span, name) = self.parse_identifier_kind();
-            params.push(Identifier { span, name });
-            if self.at(Type::Comma) {
-                self.bump_any();
+            if self.at(Type::Dot3) {
+                // The rest parameter comes last, without a default or a trailing comma
+                let rest_span = self.start_span();
+                self.bump_any(); // bump `...`
+                let argument = self.parse_binding_pattern()?;
+                rest = Some(Box::new(RestElement {
+                    span: self.end_span(rest_span),
+                    argument,
+                }));
+                break;
+            }
+            params.push(self.parse_binding_element()?);
+            if !self.at(Type::RParen) {
+                self.expect(Type::Comma)?;
             }
         }
         self.expect(Type::RParen)?;
-        Ok(Box::new(FormalParameters { span, params }))
+        let params = Box::new(FormalParameters { span, params, rest });
+        self.check_duplicate_parameters(&params, unique)?;
+        Ok(params)
+    }
+
+    /// A name can only be bound once by `unique` parameters, by parameters that are not all
+    /// plain names, and by the parameters of a function in strict mode code
+    fn check_duplicate_parameters(
+        &self,
+        params: &FormalParameters<'a>,
+        unique: bool,
+    ) -> Result<()> {
+        let simple = params.rest.is_none()
+            && params
+                .params
+                .iter()
+                .all(|param| matches!(param, BindingPattern::BindingIdentifier(_)));
+        if !unique && simple && !self.is_strict() {
+            return Ok(());
+        }
+        let mut names = vec![];
+        for param in &params.params {
+            pattern::bound_names(param, &mut names);
+        }
+        if let Some(rest) = &params.rest {
+            pattern::bound_names(&rest.argument, &mut names);
+        }
+        for (i, ident) in names.iter().enumerate() {
+            if names[..i].iter().any(|prev| prev.name == ident.name) {
+                return Err(ParseError::DuplicateParameter { span: ident.span });
+            }
+        }
+        Ok(())
     }
 
     pub(crate) fn parse_function(
         &mut self,
         span: Span,
         id: Option<Identifier<'a>>,
+        is_async: bool,
+        is_generator: bool,
     ) -> Result<Box<Function<'a>>> {
-        let params = self.parse_formal_parameters()?;
-
-        let body = if self.at(Type::LCurly) {
-            Some(self.parse_function_body()?)
-        } else {
-            None
-        };
+        let (params, body) = self.function_context(is_async, is_generator, false, |p| {
+            let params = p.parse_formal_parameters(false)?;
+            let body = if p.at(Type::LCurly) {
+                Some(p.parse_function_body()?)
+            } else {
+                None
+            };
+            Ok((params, body))
+        })?;
 
         Ok(Box::new(Function {
             span: self.end_span(span),
             id,
             params,
             body,
+            is_async,
+            is_generator,
         }))
     }
 
+    /// The parameters and body of a method, starting at `(`
+    pub(crate) fn parse_method_function(
+        &mut self,
+        is_async: bool,
+        is_generator: bool,
+    ) -> Result<Box<Function<'a>>> {
+        let span = self.start_span();
+        let (params, body) = self.function_context(is_async, is_generator, false, |p| {
+            Ok((p.parse_formal_parameters(true)?, p.parse_function_body()?))
+        })?;
+        Ok(Box::new(Function {
+            span: self.end_span(span),
+            id: None,
+            params,
+            body: Some(body),
+            is_async,
+            is_generator,
+        }))
+    }
+
+    /// Parameters and bodies see [Await] and [Yield] of their own function, not the enclosing one.
+    /// Arrow functions see `new.target` of the enclosing function.
+    pub(crate) fn function_context<T>(
+        &mut self,
+        is_async: bool,
+        is_generator: bool,
+        is_arrow: bool,
+        parse: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let (mut add, mut remove) = (Context::empty(), Context::empty());
+        if !is_arrow {
+            add = add | Context::Function;
+        }
+        match is_async {
+            true => add = add | Context::Await,
+            false => remove = remove | Context::Await,
+        }
+        match is_generator {
+            true => add = add | Context::Yield,
+            false => remove = remove | Context::Yield,
+        }
+        self.context(add, remove, parse)
+    }
+
     pub(crate) fn parse_function_body(&mut self) -> Result<Box<FunctionBody<'a>>> {
         let span = self.start_span();
         self.expect(Type::LCurly)?;
 
-        // TODO: consider context
-        // let (directives, statements) = self.context(Context::Return, Context::empty(), |p| {
-        //     p.parse_directives_and_statements(/* is_top_level */ false)
-        // })?;
-        let statements = self.parse_statements(false)?;
+        let statements =
+            self.context(Context::In, Context::empty(), |p| p.parse_statements(false))?;
 
         self.expect(Type::RCurly)?;
         Ok(Box::new(FunctionBody {
@@ -1,16 +1,22 @@
 pub mod ast;
+pub mod class;
+pub mod codegen;
+pub mod error;
 pub mod expr;
 pub mod func;
+pub mod module;
 pub mod obj;
 pub mod op;
 pub mod parser;
+pub mod pattern;
 pub mod stmt;
 pub mod syntax;
 
+pub use error::{Diagnostic, ParseError};
 pub use js_lexer::token::*;
 pub use parser::*;
 
-pub type Result<T> = std::result::Result<T, String>;
+pub type Result<T> = std::result::Result<T, ParseError>;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum StatementContext {
@@ -19,5 +25,69 @@ pub enum StatementContext {
     While,
     With,
     For,
+    Label,
     StatementList,
+    /// The top level of a module, where `import` and `export` declarations may appear
+    ModuleItem,
+}
+
+/// Grammar parameters of the production being parsed, see 5.1.5 Grammar Notation
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Context(u8);
+
+#[allow(non_upper_case_globals)]
+impl Context {
+    /// [In]: `in` is a relational operator, which it is not in the head of a `for` statement
+    pub const In: Self = Self(1 << 0);
+    /// [Await]: `await` is an operator, inside async functions and at the top level of modules
+    pub const Await: Self = Self(1 << 1);
+    /// [Yield]: `yield` is an operator, inside generator functions
+    pub const Yield: Self = Self(1 << 2);
+    /// `new.target` is allowed, inside non-arrow functions, class field initializers and
+    /// static blocks
+    pub const Function: Self = Self(1 << 3);
+    /// Strict mode code, in modules and class bodies
+    pub const Strict: Self = Self(1 << 4);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn has_in(self) -> bool {
+        self.0 & Self::In.0 != 0
+    }
+
+    pub fn has_await(self) -> bool {
+        self.0 & Self::Await.0 != 0
+    }
+
+    pub fn has_yield(self) -> bool {
+        self.0 & Self::Yield.0 != 0
+    }
+
+    pub fn has_function(self) -> bool {
+        self.0 & Self::Function.0 != 0
+    }
+
+    pub fn has_strict(self) -> bool {
+        self.0 & Self::Strict.0 != 0
+    }
+
+    fn with(self, add: Self, remove: Self) -> Self {
+        Self((self.0 | add.0) & !remove.0)
+    }
+}
+
+impl std::ops::BitOr for Context {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::In
+    }
 }
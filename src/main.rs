@@ -1,4 +1,5 @@
 // usage: ./{{project_name}} <filename>
+use js_parser::codegen::python::PythonCodegen;
 use js_parser::parser::*;
 
 fn main() {
@@ -12,19 +13,27 @@ fn main() {
 
     let source = std::fs::read_to_string(filename).unwrap();
 
-    let mut parser = Parser::new(&source);
+    let parser = Parser::new(&source);
 
-    let program = parser.parse();
+    let ParseResult {
+        program,
+        diagnostics,
+    } = parser.parse_recovering();
 
-    match program {
-        Ok(program) => {
-            // Serialize the program to a JSON string and write it to file:
-            let path = format!("{}-ast.json", filename);
-            let writer = std::fs::File::create(path).unwrap();
-            serde_json::to_writer_pretty(writer, &program).unwrap();
-        }
-        Err(e) => {
-            eprintln!("{}", e);
+    if !diagnostics.is_empty() {
+        for diagnostic in &diagnostics {
+            eprint!("{}", diagnostic.render(&source));
         }
+        std::process::exit(1);
     }
+
+    // Serialize the program to a JSON string and write it to file:
+    let path = format!("{}-ast.json", filename);
+    let writer = std::fs::File::create(path).unwrap();
+    serde_json::to_writer_pretty(writer, &program).unwrap();
+
+    // Translate the program to Python and write it next to the source:
+    let path = format!("{}.py", filename);
+    let python = PythonCodegen::new().build(&program);
+    std::fs::write(path, python).unwrap();
 }
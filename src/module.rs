@@ -0,0 +1,297 @@
+use super::*;
+use crate::ast::*;
+use syntax::lex::TokenTypeUtil;
+
+impl<'a> Parser<'a> {
+    /// Section 16.2.2 Imports
+    pub(crate) fn parse_import_declaration(
+        &mut self,
+        stmt_ctx: StatementContext,
+    ) -> Result<Statement<'a>> {
+        let span = self.start_span();
+        self.check_module_item(stmt_ctx)?;
+        self.bump_any(); // advance `import`
+
+        let mut specifiers = vec![];
+        if !self.at(Type::Str) {
+            let mut named = true;
+            if self.cur_kind().is_identifier() {
+                let specifier_span = self.start_span();
+                let local = self.parse_binding_identifier()?;
+                specifiers.push(ImportDeclarationSpecifier::ImportDefaultSpecifier(
+                    Box::new(ImportDefaultSpecifier {
+                        span: self.end_span(specifier_span),
+                        local,
+                    }),
+                ));
+                named = self.eat(Type::Comma);
+            }
+            if named {
+                match self.cur_kind() {
+                    Type::Star => specifiers.push(self.parse_import_namespace_specifier()?),
+                    _ => specifiers.extend(self.parse_import_specifiers()?),
+                }
+            }
+            self.expect(Type::From)?;
+        }
+        let source = self.parse_module_specifier()?;
+        self.auto_semicoclon_insertion()?;
+        Ok(Statement::ImportDeclaration(Box::new(ImportDeclaration {
+            span: self.end_span(span),
+            specifiers,
+            source,
+        })))
+    }
+
+    /// `* as local`
+    fn parse_import_namespace_specifier(&mut self) -> Result<ImportDeclarationSpecifier<'a>> {
+        let span = self.start_span();
+        self.bump_any(); // advance `*`
+        self.expect_as()?;
+        let local = self.parse_binding_identifier()?;
+        Ok(ImportDeclarationSpecifier::ImportNamespaceSpecifier(
+            Box::new(ImportNamespaceSpecifier {
+                span: self.end_span(span),
+                local,
+            }),
+        ))
+    }
+
+    /// `{ a, b as c, 'd' as e }`
+    fn parse_import_specifiers(&mut self) -> Result<Vec<ImportDeclarationSpecifier<'a>>> {
+        self.expect(Type::LCurly)?;
+        let mut specifiers = vec![];
+        while !self.at(Type::RCurly) {
+            let span = self.start_span();
+            let is_binding = self.cur_kind().is_identifier();
+            let imported = self.parse_module_export_name()?;
+            let local = match &imported {
+                _ if self.at_as() => {
+                    self.bump_any(); // advance `as`
+                    self.parse_binding_identifier()?
+                }
+                // `{ default }` and `{ 'a' }` need a local name
+                ModuleExportName::IdentifierName(name) if is_binding => Identifier {
+                    span: name.span,
+                    name: name.name,
+                },
+                _ => return Err(self.unexpected()),
+            };
+            specifiers.push(ImportDeclarationSpecifier::ImportSpecifier(Box::new(
+                ImportSpecifier {
+                    span: self.end_span(span),
+                    imported,
+                    local,
+                },
+            )));
+            if !self.eat(Type::Comma) {
+                break;
+            }
+        }
+        self.expect(Type::RCurly)?;
+        Ok(specifiers)
+    }
+
+    /// Section 16.2.3 Exports
+    pub(crate) fn parse_export_declaration(
+        &mut self,
+        stmt_ctx: StatementContext,
+    ) -> Result<Statement<'a>> {
+        let span = self.start_span();
+        self.check_module_item(stmt_ctx)?;
+        self.bump_any(); // advance `export`
+
+        match self.cur_kind() {
+            Type::Default => self.parse_export_default_declaration(span),
+            Type::Star => self.parse_export_all_declaration(span),
+            Type::LCurly => {
+                let (specifiers, not_local) = self.parse_export_specifiers()?;
+                let source = if self.eat(Type::From) {
+                    Some(self.parse_module_specifier()?)
+                } else if let Some(error) = not_local {
+                    return Err(error);
+                } else {
+                    None
+                };
+                self.auto_semicoclon_insertion()?;
+                Ok(Statement::ExportNamedDeclaration(Box::new(
+                    ExportNamedDeclaration {
+                        span: self.end_span(span),
+                        declaration: None,
+                        specifiers,
+                        source,
+                    },
+                )))
+            }
+            Type::Var | Type::Let | Type::Const | Type::Function | Type::Class | Type::Async
+                if !self.at(Type::Async) || self.peek_kind() == Type::Function =>
+            {
+                let declaration = self.parse_statement(StatementContext::StatementList)?;
+                Ok(Statement::ExportNamedDeclaration(Box::new(
+                    ExportNamedDeclaration {
+                        span: self.end_span(span),
+                        declaration: Some(declaration),
+                        specifiers: vec![],
+                        source: None,
+                    },
+                )))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_export_default_declaration(&mut self, span: Span) -> Result<Statement<'a>> {
+        self.bump_any(); // advance `default`
+        let declaration = match self.cur_kind() {
+            Type::Function => {
+                ExportDefaultDeclarationKind::FunctionDeclaration(self.parse_function_impl(false)?)
+            }
+            Type::Async if self.peek_kind() == Type::Function && !self.peek_is_on_new_line() => {
+                ExportDefaultDeclarationKind::FunctionDeclaration(self.parse_function_impl(false)?)
+            }
+            Type::Class => {
+                let class_span = self.start_span();
+                self.bump_any(); // advance `class`
+                ExportDefaultDeclarationKind::ClassDeclaration(self.parse_class(class_span)?)
+            }
+            _ => {
+                let expression = self.parse_assignment_expression_or_higher()?;
+                self.auto_semicoclon_insertion()?;
+                ExportDefaultDeclarationKind::Expression(expression)
+            }
+        };
+        Ok(Statement::ExportDefaultDeclaration(Box::new(
+            ExportDefaultDeclaration {
+                span: self.end_span(span),
+                declaration,
+            },
+        )))
+    }
+
+    fn parse_export_all_declaration(&mut self, span: Span) -> Result<Statement<'a>> {
+        self.bump_any(); // advance `*`
+        let exported = if self.at_as() {
+            self.bump_any(); // advance `as`
+            Some(self.parse_module_export_name()?)
+        } else {
+            None
+        };
+        self.expect(Type::From)?;
+        let source = self.parse_module_specifier()?;
+        self.auto_semicoclon_insertion()?;
+        Ok(Statement::ExportAllDeclaration(Box::new(
+            ExportAllDeclaration {
+                span: self.end_span(span),
+                exported,
+                source,
+            },
+        )))
+    }
+
+    /// `{ a, b as c, d as 'e' }`, with the error for the first name that can only be exported
+    /// from another module, like `'a'` or `default`
+    fn parse_export_specifiers(
+        &mut self,
+    ) -> Result<(Vec<ExportSpecifier<'a>>, Option<ParseError>)> {
+        self.expect(Type::LCurly)?;
+        let mut specifiers = vec![];
+        let mut not_local = None;
+        while !self.at(Type::RCurly) {
+            let span = self.start_span();
+            if !self.cur_kind().is_identifier() && not_local.is_none() {
+                not_local = Some(self.unexpected());
+            }
+            let local = self.parse_module_export_name()?;
+            let exported = if self.at_as() {
+                self.bump_any(); // advance `as`
+                self.parse_module_export_name()?
+            } else {
+                local.clone()
+            };
+            specifiers.push(ExportSpecifier {
+                span: self.end_span(span),
+                local,
+                exported,
+            });
+            if !self.eat(Type::Comma) {
+                break;
+            }
+        }
+        self.expect(Type::RCurly)?;
+        Ok((specifiers, not_local))
+    }
+
+    /// `import(source)` or `import.meta`
+    pub(crate) fn parse_import_expression(&mut self) -> Result<Expression<'a>> {
+        let span = self.start_span();
+        let meta = self.parse_identifier_name()?; // advance `import`
+        if self.eat(Type::Dot) {
+            self.expect_peek_only(Type::Meta)?;
+            let property = self.parse_identifier_name()?;
+            let span = self.end_span(span);
+            if self.source_type != SourceType::Module {
+                return Err(ParseError::ModuleSyntaxOutsideModule { span });
+            }
+            return Ok(Expression::MetaProperty(Box::new(MetaProperty {
+                span,
+                meta,
+                property,
+            })));
+        }
+
+        self.expect(Type::LParen)?;
+        let source = self.parse_assignment_expression_or_higher()?;
+        self.expect(Type::RParen)?;
+        Ok(Expression::ImportExpression(Box::new(ImportExpression {
+            span: self.end_span(span),
+            source,
+        })))
+    }
+
+    fn check_module_item(&self, stmt_ctx: StatementContext) -> Result<()> {
+        let span = self.cur_token_span();
+        match (self.source_type, stmt_ctx) {
+            (_, StatementContext::ModuleItem) => Ok(()),
+            (SourceType::Module, _) => Err(ParseError::ModuleDeclarationNotTopLevel { span }),
+            (SourceType::Script, _) => Err(ParseError::ModuleSyntaxOutsideModule { span }),
+        }
+    }
+
+    fn parse_module_specifier(&mut self) -> Result<StringLiteral<'a>> {
+        self.expect_peek_only(Type::Str)?;
+        self.parse_literal_string()
+    }
+
+    fn parse_module_export_name(&mut self) -> Result<ModuleExportName<'a>> {
+        match self.cur_kind() {
+            Type::Str => Ok(ModuleExportName::StringLiteral(
+                self.parse_literal_string()?,
+            )),
+            kind if kind.is_identifier_name() => Ok(ModuleExportName::IdentifierName(
+                self.parse_identifier_name()?,
+            )),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_binding_identifier(&mut self) -> Result<Identifier<'a>> {
+        if !self.cur_kind().is_identifier() {
+            return Err(self.unexpected());
+        }
+        let (span, name) = self.parse_identifier_kind();
+        Ok(Identifier { span, name })
+    }
+
+    /// `as` is not a keyword, it is lexed as an identifier
+    fn at_as(&self) -> bool {
+        self.at(Type::Identifier) && self.cur_string() == "as"
+    }
+
+    fn expect_as(&mut self) -> Result<()> {
+        if !self.at_as() {
+            return Err(self.unexpected());
+        }
+        self.bump_any();
+        Ok(())
+    }
+}
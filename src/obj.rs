@@ -1,19 +1,23 @@
 use super::*;
 use crate::ast::*;
+use syntax::lex::TokenTypeUtil;
+use syntax::operator::AssignmentOperator;
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_object_expression(&mut self) -> Result<Expression<'a>> {
         let span = self.start_span();
         self.expect(Type::LCurly)?;
         let mut properties = vec![];
+        let mut trailing_comma = None;
         while !self.at(Type::RCurly) {
             let prop = self.parse_object_property()?;
             properties.push(prop);
-            if self.at(Type::Comma) {
-                self.bump_any();
+            if !self.at(Type::RCurly) {
+                let comma = self.cur_token_span();
+                self.expect(Type::Comma)?;
+                trailing_comma = self.at(Type::RCurly).then_some(comma);
             }
         }
-        let trailing_comma = self.at(Type::Comma).then(|| self.start_span());
         self.expect(Type::RCurly)?;
         Ok(Expression::ObjectExpression(Box::new(ObjectExpression {
             span: self.end_span(span),
@@ -21,20 +25,110 @@ impl<'a> Parser<'a> {
             trailing_comma,
         })))
     }
-    pub(crate) fn parse_object_property(&mut self) -> Result<ObjectProperty<'a>> {
+    /// Section 13.2.5 Object Initializer, a property, method, accessor or spread
+    pub(crate) fn parse_object_property(&mut self) -> Result<ObjectPropertyKind<'a>> {
         let span = self.start_span();
-        let key = self.parse_property_key()?;
-        self.expect(Type::Colon)?;
-        let value = self.parse_assignment_expression_or_higher()?;
-        Ok(ObjectProperty {
+        if self.at(Type::Dot3) {
+            let spread = self.parse_spread_element()?;
+            return Ok(ObjectPropertyKind::SpreadProperty(Box::new(spread)));
+        }
+
+        // `get`, `set` and `async` are only modifiers when another name follows
+        let modifier = self.cur_kind();
+        let mut is_generator = self.eat(Type::Star);
+        let mut is_async = false;
+        let mut kind = PropertyKind::Init;
+        let mut key = self.parse_property_key()?;
+        if !is_generator && !self.at_property_name_end() {
+            match modifier {
+                Type::Get => kind = PropertyKind::Get,
+                Type::Set => kind = PropertyKind::Set,
+                // [no LineTerminator here] after `async`
+                Type::Async if !self.cur_token().is_on_new_line => {
+                    is_async = true;
+                    is_generator = self.eat(Type::Star);
+                }
+                _ => return Err(self.unexpected()),
+            }
+            key = self.parse_property_key()?;
+        }
+
+        if kind == PropertyKind::Init && (is_async || is_generator || self.at(Type::LParen)) {
+            kind = PropertyKind::Method;
+        }
+        if kind != PropertyKind::Init {
+            let value = self.parse_method_function(is_async, is_generator)?;
+            let value = Expression::FunctionExpression(value);
+            return Ok(self.object_property(span, kind, key, value, false));
+        }
+
+        if self.eat(Type::Colon) {
+            let value = self.context(Context::In, Context::empty(), |p| {
+                p.parse_assignment_expression_or_higher()
+            })?;
+            return Ok(self.object_property(span, kind, key, value, false));
+        }
+
+        // `{ a }`, or `{ a = 1 }` when the object is reinterpreted as a pattern
+        let ident = match &key {
+            PropertyKey::IdentifierName(name) if modifier.is_identifier() => Identifier {
+                span: name.span,
+                name: name.name,
+            },
+            _ => {
+                return Err(ParseError::ExpectedToken {
+                    span: self.cur_token_span(),
+                    expected: Type::Colon,
+                    found: self.cur_kind(),
+                })
+            }
+        };
+        let value = if self.eat(Type::Eq) {
+            let right = self.context(Context::In, Context::empty(), |p| {
+                p.parse_assignment_expression_or_higher()
+            })?;
+            let span = self.end_span(span);
+            self.cover_initialized_names.push(span);
+            Expression::AssignmentExpression(Box::new(AssignmentExpression {
+                span,
+                operator: AssignmentOperator::Assign,
+                left: AssignmentTarget::Identifier(Box::new(ident)),
+                right,
+            }))
+        } else {
+            Expression::Identifier(Box::new(ident))
+        };
+        Ok(self.object_property(span, kind, key, value, true))
+    }
+
+    fn object_property(
+        &self,
+        span: Span,
+        kind: PropertyKind,
+        key: PropertyKey<'a>,
+        value: Expression<'a>,
+        shorthand: bool,
+    ) -> ObjectPropertyKind<'a> {
+        ObjectPropertyKind::ObjectProperty(Box::new(ObjectProperty {
             span: self.end_span(span),
+            kind,
             key,
             value,
-        })
+            shorthand,
+        }))
+    }
+
+    /// Whether the name just parsed is the whole key of a property or method
+    fn at_property_name_end(&self) -> bool {
+        matches!(
+            self.cur_kind(),
+            Type::LParen | Type::Colon | Type::Comma | Type::RCurly | Type::Eq
+        )
     }
+
     pub(crate) fn parse_property_key(&mut self) -> Result<PropertyKey<'a>> {
         let key = match self.cur_kind() {
-            Type::Identifier => {
+            kind if kind.is_identifier_name() => {
                 let (span, name) = self.parse_identifier_kind();
                 PropertyKey::IdentifierName(IdentifierName { span, name })
             }
@@ -46,6 +140,14 @@ impl<'a> Parser<'a> {
                 let lit = self.parse_literal_number()?;
                 PropertyKey::NumericLiteral(lit)
             }
+            Type::LBrack => {
+                self.bump_any(); // bump `[`
+                let expression = self.context(Context::In, Context::empty(), |p| {
+                    p.parse_assignment_expression_or_higher()
+                })?;
+                self.expect(Type::RBrack)?;
+                PropertyKey::Computed(expression)
+            }
             _ => {
                 return Err(self.unexpected());
             }
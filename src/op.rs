@@ -9,7 +9,7 @@ use super::Type;
 
 pub fn kind_to_precedence(kind: Type) -> Option<Precedence> {
     match kind {
-        // Type::Question2 => Some(Precedence::NullishCoalescing),
+        Type::Question2 => Some(Precedence::NullishCoalescing),
         Type::Pipe2 => Some(Precedence::LogicalOr),
         Type::Amp2 => Some(Precedence::LogicalAnd),
         Type::Pipe => Some(Precedence::BitwiseOr),
@@ -22,7 +22,7 @@ pub fn kind_to_precedence(kind: Type) -> Option<Precedence> {
         Type::ShiftLeft | Type::ShiftRight | Type::ShiftRight3 => Some(Precedence::Shift),
         Type::Plus | Type::Minus => Some(Precedence::Add),
         Type::Star | Type::Slash | Type::Percent => Some(Precedence::Multiply),
-        // Type::Star2 => Some(Precedence::Exponentiation),
+        Type::Star2 => Some(Precedence::Exponentiation),
         // Type::As | Type::Satisfies if is_typescript => Some(Precedence::Compare),
         _ => None,
     }
@@ -51,7 +51,7 @@ pub fn map_binary_operator(kind: Type) -> BinaryOperator {
         Type::Amp => BinaryOperator::BitwiseAnd,
         Type::In => BinaryOperator::In,
         Type::Instanceof => BinaryOperator::Instanceof,
-        // Type::Star2 => BinaryOperator::Exponential,
+        Type::Star2 => BinaryOperator::Exponential,
         _ => unreachable!("Binary Operator: {kind:?}"),
     }
 }
@@ -73,7 +73,7 @@ pub fn map_logical_operator(kind: Type) -> LogicalOperator {
     match kind {
         Type::Pipe2 => LogicalOperator::Or,
         Type::Amp2 => LogicalOperator::And,
-        // Type::Question2 => LogicalOperator::Coalesce,
+        Type::Question2 => LogicalOperator::Coalesce,
         _ => unreachable!("Logical Operator: {kind:?}"),
     }
 }
@@ -100,10 +100,10 @@ pub fn map_assignment_operator(kind: Type) -> AssignmentOperator {
         Type::PipeEq => AssignmentOperator::BitwiseOR,
         Type::CaretEq => AssignmentOperator::BitwiseXOR,
         Type::AmpEq => AssignmentOperator::BitwiseAnd,
-        // Type::Amp2Eq => AssignmentOperator::LogicalAnd,
-        // Type::Pipe2Eq => AssignmentOperator::LogicalOr,
-        // Type::Question2Eq => AssignmentOperator::LogicalNullish,
-        // Type::Star2Eq => AssignmentOperator::Exponential,
+        Type::Amp2Eq => AssignmentOperator::LogicalAnd,
+        Type::Pipe2Eq => AssignmentOperator::LogicalOr,
+        Type::Question2Eq => AssignmentOperator::LogicalNullish,
+        Type::Star2Eq => AssignmentOperator::Exponential,
         _ => unreachable!("Update Operator: {kind:?}"),
     }
 }
@@ -1,52 +1,142 @@
 use super::*;
 use crate::ast::*;
-use std::sync::mpsc;
 
-use js_lexer::lexer::token_stream;
+use js_lexer::lexer::Lexer;
 use syntax::lex::TokenTypeUtil;
 
 pub struct Parser<'a> {
     /// Source Code
     source: &'a str,
 
-    lexer: mpsc::Receiver<Token>,
+    lexer: Lexer<'a>,
 
     /// Current Token consumed from the lexer
     cur_token: Token,
 
+    /// The token after `cur_token`, if it was read ahead by `peek_kind`
+    peeked: Option<Token>,
+
     /// The end range of the previous token
     prev_token_end: usize,
+
+    /// Whether `import` and `export` declarations are allowed at the top level
+    pub(crate) source_type: SourceType,
+
+    /// Grammar parameters of the production being parsed
+    pub(crate) ctx: Context,
+
+    /// Resynchronize after a statement fails to parse instead of bailing out
+    pub(crate) recover: bool,
+
+    /// Errors collected in recovery mode
+    pub(crate) errors: Vec<ParseError>,
+
+    /// The rest parameter of the parenthesized arrow function parameters just parsed
+    pub(crate) cover_rest: Option<Box<RestElement<'a>>>,
+
+    /// `a = 1` properties of the object literals parsed so far, an error unless the objects
+    /// are reinterpreted as patterns
+    pub(crate) cover_initialized_names: Vec<Span>,
+
+    /// The last `await` parsed, as an identifier or an expression, which the parameters of an
+    /// async arrow function must not contain
+    pub(crate) last_await: Option<Span>,
+
+    /// Labels of the enclosing statements in the current function body, innermost last
+    pub(crate) labels: Vec<Label<'a>>,
+}
+
+/// A label in scope, see [`Parser::labels`]
+pub(crate) struct Label<'a> {
+    pub(crate) name: &'a str,
+    /// Whether the label is a target for `continue`
+    pub(crate) is_loop: bool,
+    /// Whether the labeled statement is itself labeled, `a: b: ...`
+    pub(crate) chained: bool,
+}
+
+/// The outcome of [`Parser::parse_recovering`]
+#[derive(Debug)]
+pub struct ParseResult<'a> {
+    /// The parsed program, with [`Statement::Error`] in place of statements that failed to parse
+    pub program: Program<'a>,
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(source: &'a str) -> Self {
         Self {
             source,
-            lexer: token_stream(source),
+            lexer: Lexer::new(source),
             cur_token: Token::default(),
+            peeked: None,
             prev_token_end: 0,
+            source_type: SourceType::Script,
+            ctx: Context::default(),
+            recover: false,
+            errors: vec![],
+            cover_rest: None,
+            cover_initialized_names: vec![],
+            last_await: None,
+            labels: vec![],
         }
     }
 
-    pub fn parse(&mut self) -> Result<Program> {
+    pub fn parse(&mut self) -> Result<Program<'a>> {
         self.parse_program()
     }
 
+    /// Parse the source as a module, which may contain `import` and `export` declarations
+    pub fn parse_module(&mut self) -> Result<Program<'a>> {
+        self.source_type = SourceType::Module;
+        self.parse_program()
+    }
+
+    /// Parse the whole source, collecting every error instead of stopping at the first one
+    pub fn parse_recovering(mut self) -> ParseResult<'a> {
+        self.recover = true;
+        let program = match self.parse_program() {
+            Ok(program) => program,
+            Err(error) => {
+                // Only errors outside of any statement list end up here
+                self.errors.push(error);
+                Program {
+                    span: Span::new(0, self.source.len()),
+                    source_type: self.source_type,
+                    body: vec![],
+                    source_text: self.source,
+                }
+            }
+        };
+        ParseResult {
+            program,
+            diagnostics: self.errors,
+        }
+    }
+
     pub fn parse_expression(mut self) -> Result<Expression<'a>> {
         // initialize cur_token and prev_token by moving onto the first token
         self.bump_any();
         let expr = self.parse_expr()?;
-        Ok(expr)
+        match self.cover_initialized_names.first() {
+            Some(&span) => Err(ParseError::InvalidShorthandInitializer { span }),
+            None => Ok(expr),
+        }
     }
 
     fn parse_program(&mut self) -> Result<Program<'a>> {
         // initialize cur_token and prev_token by moving onto the first token
         self.bump_any();
         let start = self.start_span();
+        if self.source_type == SourceType::Module {
+            // top-level await, and modules are strict mode code
+            self.ctx = self.ctx | Context::Await | Context::Strict;
+        }
         let body = self.parse_statements(true)?;
         let end = self.prev_token_end;
         Ok(Program {
             span: Span::new(start.start, end),
+            source_type: self.source_type,
             body,
             source_text: self.source,
         })
@@ -70,6 +160,11 @@ impl<'a> Parser<'a> {
         &self.cur_token
     }
 
+    /// The span of the current token
+    pub(crate) fn cur_token_span(&self) -> Span {
+        Span::new(self.cur_token.start, self.cur_token.end)
+    }
+
     pub(crate) fn cur_kind(&self) -> Type {
         self.cur_token.typ
     }
@@ -106,27 +201,76 @@ impl<'a> Parser<'a> {
         false
     }
 
+    /// Parse with the grammar parameters in `add` turned on and those in `remove` turned off
+    pub(crate) fn context<T>(
+        &mut self,
+        add: Context,
+        remove: Context,
+        parse: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let ctx = self.ctx;
+        self.ctx = ctx.with(add, remove);
+        let result = parse(self);
+        self.ctx = ctx;
+        result
+    }
+
+    /// The kind of the token after the current one
+    pub(crate) fn peek_kind(&mut self) -> Type {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.next_token());
+        }
+        self.peeked.map_or(Type::EOF, |token| token.typ)
+    }
+
+    /// Whether a line terminator comes before the token after the current one
+    pub(crate) fn peek_is_on_new_line(&mut self) -> bool {
+        self.peek_kind();
+        self.peeked.is_some_and(|token| token.is_on_new_line)
+    }
+
     /// Move to the next token
     pub(crate) fn advance(&mut self) {
-        let mut token = Token::default();
-        while let Ok(t) = self.lexer.recv() {
-            if matches!(t.typ, Type::LineTerminator) {
-                continue;
-            } else {
-                token = t;
-                break;
-            }
-        }
+        let token = match self.peeked.take() {
+            Some(token) => token,
+            None => self.next_token(),
+        };
         self.prev_token_end = self.cur_token.end;
         self.cur_token = token;
     }
 
+    /// Lex the current `/` or `/=` token again as the start of a regular expression literal
+    pub(crate) fn relex_regex(&mut self) {
+        // a token starting an expression is never read past
+        self.peeked = None;
+        let token = self.lexer.relex_regex(self.cur_token.start);
+        self.cur_token = Token {
+            is_on_new_line: self.cur_token.is_on_new_line,
+            ..token
+        };
+    }
+
+    fn next_token(&mut self) -> Token {
+        self.lexer
+            .find(|t| !matches!(t.typ, Type::LineTerminator))
+            .unwrap_or(Token {
+                typ: Type::EOF,
+                start: self.source.len(),
+                end: self.source.len(),
+                is_on_new_line: false,
+            })
+    }
+
     pub(crate) fn expect_peek_only(&mut self, kind: Type) -> Result<()> {
+        if self.at(Type::LexerError) {
+            return Err(self.unexpected());
+        }
         if !self.at(kind) {
-            return Err(format!(
-                "Expected token type {:?} but got {:?}",
-                kind, self.cur_token
-            ));
+            return Err(ParseError::ExpectedToken {
+                span: self.cur_token_span(),
+                expected: kind,
+                found: self.cur_kind(),
+            });
         }
         Ok(())
     }
@@ -137,8 +281,23 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    pub(crate) fn unexpected(&self) -> String {
-        format!("Unexpected token: {:?}", self.cur_token)
+    /// The error for the current token, or the lexer's error if it could not produce one
+    pub(crate) fn unexpected(&self) -> ParseError {
+        if let Some(error) = self.lexer.error().filter(|_| self.at(Type::LexerError)) {
+            return ParseError::Lex {
+                span: Span::new(error.start, error.end),
+                kind: error.kind,
+            };
+        }
+        ParseError::UnexpectedToken {
+            span: self.cur_token_span(),
+            found: self.cur_kind(),
+        }
+    }
+
+    /// Whether the code being parsed is strict mode code
+    pub(crate) fn is_strict(&self) -> bool {
+        self.ctx.has_strict()
     }
 
     pub(crate) fn can_insert_semicolon(&self) -> bool {
@@ -146,18 +305,16 @@ impl<'a> Parser<'a> {
         if kind == Type::Semicolon {
             return true;
         }
-        // TODO: check if cur_token is on a new line
-        kind == Type::RCurly || kind.is_eof()
-        // || self.cur_token().is_on_new_line
+        kind == Type::RCurly || kind.is_eof() || self.cur_token().is_on_new_line
     }
 
     pub(crate) fn auto_semicoclon_insertion(&mut self) -> Result<()> {
+        if self.at(Type::LexerError) {
+            return Err(self.unexpected());
+        }
         if !self.can_insert_semicolon() {
             let span = Span::new(self.prev_token_end, self.prev_token_end);
-            return Err(format!(
-                "Expected a semicolon or an implicit semicolon after a statement, but found none at {:?}",
-                span
-            ));
+            return Err(ParseError::MissingSemicolon { span });
         }
         if self.at(Type::Semicolon) {
             self.advance();
@@ -171,6 +328,7 @@ mod test {
     use core::panic;
 
     use super::*;
+    use crate::syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator};
     #[test]
     fn parse_empty_smoke_test() {
         let source = "";
@@ -234,6 +392,491 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_multiple_declarators_test() {
+        let source = "let a = 1, b, c = a, d;\nfor (var i = 0, n = 2; i < n; i++) {}";
+        let mut parser = Parser::new(source);
+        let program = parser.parse().unwrap();
+        let Statement::VariableDeclarationStatement(decl) = &program.body[0] else {
+            panic!()
+        };
+        assert_eq!(decl.span, Span::new(0, 22));
+        let names: Vec<_> = decl
+            .declarations
+            .iter()
+            .map(|d| match &d.id {
+                BindingPattern::BindingIdentifier(ident) => ident.name,
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(names, ["a", "b", "c", "d"]);
+        assert!(decl.declarations[1].init.is_none());
+        assert_eq!(decl.declarations[2].span, Span::new(14, 19));
+        let Statement::ForStatement(stmt) = &program.body[1] else {
+            panic!()
+        };
+        let Some(ForStatementInit::VariableDeclaration(init)) = &stmt.init else {
+            panic!()
+        };
+        assert_eq!(init.declarations.len(), 2);
+
+        assert!(Parser::new("var a,;").parse().is_err());
+
+        // A variable statement ends with a semicolon, inserted only at a line break or `}`
+        let program = Parser::new("var a = 1; { let b = a }\nconst c = 2")
+            .parse()
+            .unwrap();
+        assert_eq!(program.body.len(), 3);
+        assert!(matches!(
+            Parser::new("var a = 1 b = 2").parse(),
+            Err(ParseError::MissingSemicolon { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_binding_pattern_test() {
+        let source = "let { a, b: [c, , d = 1], ...e } = f;";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::VariableDeclarationStatement(decl) = &program.body[0] else {
+            panic!()
+        };
+        let BindingPattern::ObjectPattern(object) = &decl.declarations[0].id else {
+            panic!()
+        };
+        assert_eq!(object.span, Span::new(4, 32));
+        assert!(object.properties[0].shorthand);
+        let BindingPattern::ArrayPattern(array) = &object.properties[1].value else {
+            panic!()
+        };
+        assert!(array.elements[1].is_none());
+        assert!(matches!(
+            array.elements[2],
+            Some(BindingPattern::AssignmentPattern(_))
+        ));
+        assert!(object.rest.is_some());
+
+        let program = Parser::new("[a, [b.c = 1]] = d; (({ x: y }) => y);")
+            .parse()
+            .unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        assert!(matches!(
+            &stmt.expression,
+            Expression::AssignmentExpression(assign)
+                if matches!(assign.left, AssignmentTarget::ArrayAssignmentTarget(_))
+        ));
+
+        assert!(matches!(
+            Parser::new("let [a];").parse(),
+            Err(ParseError::MissingDestructuringInitializer { .. })
+        ));
+        assert!(Parser::new("for (let [a] of b);").parse().is_ok());
+        assert!(Parser::new("let { ...[a] } = b;").parse().is_err());
+        assert!(Parser::new("let [...a, b] = c;").parse().is_err());
+        assert!(Parser::new("let { 'a' } = b;").parse().is_err());
+        assert!(Parser::new("[a] += 1;").parse().is_err());
+        assert!(Parser::new("[f()] = 1;").parse().is_err());
+        assert!(Parser::new("([a.b]) => 1;").parse().is_err());
+
+        // Parentheses may wrap a simple target, but not a pattern
+        for source in [
+            "(a) = 1;",
+            "[(a)] = b;",
+            "({ a: (b) } = c);",
+            "({ a: (b.c) = 1 } = d);",
+            "for ((a) of b);",
+        ] {
+            assert!(Parser::new(source).parse().is_ok(), "{source}");
+        }
+        for source in [
+            "({ a }) = 1;",
+            "[([a])] = b;",
+            "({ a: ({ b }) } = c);",
+            "[(a = 1)] = b;",
+            "for (([a]) of b);",
+        ] {
+            assert!(
+                matches!(
+                    Parser::new(source).parse(),
+                    Err(ParseError::InvalidAssignmentTarget { .. })
+                ),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_spread_and_rest_test() {
+        let program = Parser::new("f(a, ...b); x = [...c, d]; ((e, ...f) => f);")
+            .parse()
+            .unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::CallExpression(call) = &stmt.expression else {
+            panic!()
+        };
+        let Argument::SpreadElement(spread) = &call.arguments[1] else {
+            panic!()
+        };
+        assert_eq!(spread.span, Span::new(5, 9));
+        let Statement::ExpressionStatement(stmt) = &program.body[2] else {
+            panic!()
+        };
+        let Expression::ParenthesizedExpression(paren) = &stmt.expression else {
+            panic!()
+        };
+        let Expression::ArrowFunctionExpression(arrow) = &paren.expression else {
+            panic!()
+        };
+        assert_eq!(arrow.params.params.len(), 1);
+        assert!(arrow.params.rest.is_some());
+
+        let program = Parser::new("function g(a = 1, ...[b]) {} [c, ...d.e] = h;")
+            .parse()
+            .unwrap();
+        let Statement::FunctionDeclaration(func) = &program.body[0] else {
+            panic!()
+        };
+        assert!(matches!(
+            &func.params.rest,
+            Some(rest) if matches!(rest.argument, BindingPattern::ArrayPattern(_))
+        ));
+
+        assert!(Parser::new("function f(...a, b) {}").parse().is_err());
+        assert!(Parser::new("function f(...a = 1) {}").parse().is_err());
+        assert!(Parser::new("(...a);").parse().is_err());
+        assert!(Parser::new("[...a, b] = c;").parse().is_err());
+        assert!(Parser::new("[...a = 1] = c;").parse().is_err());
+        assert!(Parser::new("([...a, b]) => 1;").parse().is_err());
+        // A rest element is never followed by a comma, the trailing comma of a literal is fine
+        for (source, span) in [
+            ("[...a,] = b;", Span::new(5, 6)),
+            ("({...a,} = b);", Span::new(6, 7)),
+        ] {
+            assert_eq!(
+                Parser::new(source).parse().unwrap_err(),
+                ParseError::InvalidAssignmentTarget { span },
+                "{source}"
+            );
+        }
+        assert!(Parser::new("([...a,]) => 1;").parse().is_err());
+        assert!(Parser::new("({...a,}) => 1;").parse().is_err());
+        assert!(
+            Parser::new("[a,] = b; ({ a, } = b); x = [...a,]; x = {...a,};")
+                .parse()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn parse_template_literal_test() {
+        let source = "`a${b}\\x41${c}` + tag`\\unicode${d}`;";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::BinaryExpression(binary) = &stmt.expression else {
+            panic!()
+        };
+        let Expression::TemplateLiteral(template) = &binary.left else {
+            panic!()
+        };
+        assert_eq!(template.span, Span::new(0, 15));
+        assert_eq!(template.quasis.len(), 3);
+        assert_eq!(template.expressions.len(), 2);
+        assert_eq!(template.quasis[1].span, Span::new(6, 10));
+        assert_eq!(template.quasis[1].value.raw, "\\x41");
+        assert_eq!(template.quasis[1].value.cooked.as_deref(), Some("A"));
+        assert!(template.quasis[2].tail);
+        let Expression::TaggedTemplateExpression(tagged) = &binary.right else {
+            panic!()
+        };
+        assert_eq!(tagged.quasi.quasis[0].value.cooked, None);
+
+        assert!(matches!(
+            Parser::new("`\\unicode`;").parse(),
+            Err(ParseError::InvalidTemplateEscape { span }) if span == Span::new(1, 9)
+        ));
+        assert!(Parser::new("`${}`;").parse().is_err());
+        assert!(Parser::new("`${a b}`;").parse().is_err());
+    }
+
+    #[test]
+    fn parse_regexp_literal_test() {
+        let source = "x = a / b / /[/]\\//gi;\n/=a/.test(x);";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::AssignmentExpression(assign) = &stmt.expression else {
+            panic!()
+        };
+        let Expression::BinaryExpression(binary) = &assign.right else {
+            panic!()
+        };
+        let Expression::RegExpLiteral(regexp) = &binary.right else {
+            panic!()
+        };
+        assert_eq!(regexp.span, Span::new(12, 21));
+        assert_eq!((regexp.pattern, regexp.flags), ("[/]\\/", "gi"));
+        let Statement::ExpressionStatement(stmt) = &program.body[1] else {
+            panic!()
+        };
+        let Expression::CallExpression(call) = &stmt.expression else {
+            panic!()
+        };
+        assert!(matches!(
+            &call.callee,
+            Expression::StaticMemberExpression(member)
+                if matches!(member.object, Expression::RegExpLiteral(_))
+        ));
+
+        assert!(matches!(
+            Parser::new("/a/gmg;").parse(),
+            Err(ParseError::InvalidRegExpFlag { span }) if span == Span::new(5, 6)
+        ));
+        assert!(Parser::new("/a/x;").parse().is_err());
+        assert!(Parser::new("/a/uv;").parse().is_err());
+        assert!(matches!(
+            Parser::new("x = /a\n/;").parse(),
+            Err(ParseError::Lex { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_optional_chain_test() {
+        let source = "a?.b.c(d)?.[e];";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::ChainExpression(chain) = &stmt.expression else {
+            panic!()
+        };
+        assert_eq!(chain.span, Span::new(0, 14));
+        let Expression::ComputedMemberExpression(computed) = &chain.expression else {
+            panic!()
+        };
+        assert!(computed.optional);
+        let Expression::CallExpression(call) = &computed.object else {
+            panic!()
+        };
+        assert!(!call.optional);
+        let Expression::StaticMemberExpression(member) = &call.callee else {
+            panic!()
+        };
+        assert!(!member.optional);
+        assert!(matches!(
+            &member.object,
+            Expression::StaticMemberExpression(member) if member.optional
+        ));
+
+        // The chain ends at the parentheses
+        let program = Parser::new("(a?.b).c;").parse().unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        assert!(matches!(
+            &stmt.expression,
+            Expression::StaticMemberExpression(member) if !member.optional
+        ));
+        assert!(Parser::new("a?.b = c;").parse().is_err());
+        assert!(Parser::new("new a?.b();").parse().is_err());
+        assert!(Parser::new("a?.b`c`;").parse().is_err());
+        assert!(Parser::new("a?.`c`;").parse().is_err());
+    }
+
+    #[test]
+    fn parse_nullish_coalescing_test() {
+        let program = Parser::new("a ?? b ?? c; a ??= (b || c) ?? d;")
+            .parse()
+            .unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::LogicalExpression(logical) = &stmt.expression else {
+            panic!()
+        };
+        assert_eq!(logical.operator, LogicalOperator::Coalesce);
+        assert!(matches!(&logical.left, Expression::LogicalExpression(_)));
+        let Statement::ExpressionStatement(stmt) = &program.body[1] else {
+            panic!()
+        };
+        assert!(matches!(
+            &stmt.expression,
+            Expression::AssignmentExpression(assign)
+                if assign.operator == AssignmentOperator::LogicalNullish
+        ));
+
+        for source in [
+            "a ?? b || c;",
+            "a || b ?? c;",
+            "a && b ?? c;",
+            "a ?? b && c;",
+        ] {
+            assert!(matches!(
+                Parser::new(source).parse(),
+                Err(ParseError::MixedCoalesce { span }) if span == Span::new(0, 11)
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_exponentiation_test() {
+        let program = Parser::new("a ** b ** c; (-a) ** b; a **= b; a &&= b; a ||= b;")
+            .parse()
+            .unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::BinaryExpression(binary) = &stmt.expression else {
+            panic!()
+        };
+        // Right-associative
+        assert_eq!(binary.operator, BinaryOperator::Exponential);
+        assert!(matches!(&binary.left, Expression::Identifier(_)));
+        assert!(matches!(&binary.right, Expression::BinaryExpression(_)));
+        let operators = program.body[2..]
+            .iter()
+            .map(|stmt| match stmt {
+                Statement::ExpressionStatement(stmt) => match &stmt.expression {
+                    Expression::AssignmentExpression(assign) => assign.operator,
+                    _ => panic!(),
+                },
+                _ => panic!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            operators,
+            [
+                AssignmentOperator::Exponential,
+                AssignmentOperator::LogicalAnd,
+                AssignmentOperator::LogicalOr
+            ]
+        );
+
+        assert!(matches!(
+            Parser::new("x = -a ** b;").parse(),
+            Err(ParseError::UnaryBeforeExponentiation { span }) if span == Span::new(4, 6)
+        ));
+        assert!(Parser::new("x = a * typeof b ** c;").parse().is_err());
+        assert!(Parser::new("x = ++a ** -b;").parse().is_ok());
+    }
+
+    #[test]
+    fn parse_for_in_of_statement_test() {
+        // `for await` needs [Await], which the top level of a module has
+        let source = "for (const k in o) {}\nfor await (a.b of c);\nfor ((a in b);;);";
+        let program = Parser::new(source).parse_module().unwrap();
+        let Statement::ForInStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        assert_eq!(stmt.span, Span::new(0, 21));
+        assert!(matches!(
+            stmt.left,
+            ForStatementLeft::VariableDeclaration(_)
+        ));
+        let Statement::ForOfStatement(stmt) = &program.body[1] else {
+            panic!()
+        };
+        assert!(stmt.r#await);
+        assert!(matches!(
+            stmt.left,
+            ForStatementLeft::AssignmentTarget(AssignmentTarget::StaticMemberExpression(_))
+        ));
+        assert!(matches!(&program.body[2], Statement::ForStatement(_)));
+
+        // The right side of for-of is a single assignment expression
+        assert!(Parser::new("for (x of a, b);").parse().is_err());
+        assert!(Parser::new("for (a in b;;);").parse().is_err());
+        assert!(Parser::new("for (f() in b);").parse().is_err());
+        assert!(Parser::new("for await (x in y);").parse().is_err());
+        // `for (async of` would be read as an async arrow function
+        assert_eq!(
+            Parser::new("for (async of [1]);").parse().unwrap_err(),
+            ParseError::UnexpectedToken {
+                span: Span::new(5, 10),
+                found: Type::Async
+            }
+        );
+        assert!(Parser::new("for ((async) of [1]);").parse().is_ok());
+        assert!(
+            Parser::new("async function f() { for await (async of a); }")
+                .parse()
+                .is_ok()
+        );
+        assert!(matches!(
+            Parser::new("for (var a = 1 in b);").parse(),
+            Err(ParseError::InvalidForInOfDeclaration { .. })
+        ));
+        assert!(matches!(
+            Parser::new("for (let a, b of c);").parse(),
+            Err(ParseError::InvalidForInOfDeclaration { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_labeled_statement_test() {
+        let source = "a: b: for (;;) { c: { break c; } while (d) continue a; break b; }";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::LabeledStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        assert_eq!(stmt.label.name, "a");
+        assert!(matches!(&stmt.body, Statement::LabeledStatement(_)));
+
+        // A label on the next line starts an expression statement
+        let program = Parser::new("e: while (f) { break\ne; }").parse().unwrap();
+        let Statement::LabeledStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Statement::WhileStatement(stmt) = &stmt.body else {
+            panic!()
+        };
+        let Statement::BlockStatement(block) = &stmt.body else {
+            panic!()
+        };
+        assert!(matches!(&block.body[0], Statement::BreakStatement(stmt) if stmt.label.is_none()));
+
+        // Labels are scoped to the labeled statement and the enclosing function
+        assert!(Parser::new("a: { a: ; }").parse().is_err());
+        assert!(Parser::new("a: ; a: ;").parse().is_ok());
+        assert!(matches!(
+            Parser::new("a: { b: ; break b; }").parse(),
+            Err(ParseError::UndefinedLabel { span }) if span == Span::new(16, 17)
+        ));
+        assert!(Parser::new("a: while (b) { () => { break a; }; }")
+            .parse()
+            .is_err());
+        assert!(matches!(
+            Parser::new("a: { while (b) continue a; }").parse(),
+            Err(ParseError::InvalidContinueLabel { span }) if span == Span::new(24, 25)
+        ));
+        assert!(Parser::new("a: b: do continue a; while (c)")
+            .parse()
+            .is_ok());
+        assert!(Parser::new("a: switch (b) { case 1: continue a; }")
+            .parse()
+            .is_err());
+
+        // `async`, and `await` and `yield` outside of their functions, are labels in scripts
+        for source in [
+            "async: while (1) break async;",
+            "yield: 1;",
+            "await: for (;;) { a: yield: continue await; }",
+        ] {
+            let program = Parser::new(source).parse().unwrap();
+            assert!(
+                matches!(&program.body[0], Statement::LabeledStatement(_)),
+                "{source}"
+            );
+        }
+        assert!(Parser::new("function* g() { yield: 1; }").parse().is_err());
+        assert!(Parser::new("await: 1;").parse_module().is_err());
+    }
+
     #[test]
     fn parse_if_statement_test() {
         let source = "if (a) { c = a } else { return 1 }";
@@ -321,6 +964,22 @@ mod test {
                 panic!()
             }
         }
+
+        // Elements are separated by commas
+        assert_eq!(
+            Parser::new("[a b]").parse_expression().unwrap_err(),
+            ParseError::ExpectedToken {
+                span: Span::new(3, 4),
+                expected: Type::Comma,
+                found: Type::Identifier
+            }
+        );
+        let Expression::ArrayExpression(array) =
+            Parser::new("[a, , b,]").parse_expression().unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!(array.elements.len(), 3);
     }
 
     #[test]
@@ -340,6 +999,74 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_object_literal_test() {
+        let source =
+            "x = { a, [b]: 1, c() {}, get d() {}, set d(v) {}, async e() {}, *f() {}, ...g };";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::AssignmentExpression(assign) = &stmt.expression else {
+            panic!()
+        };
+        let Expression::ObjectExpression(object) = &assign.right else {
+            panic!()
+        };
+        let properties = object
+            .properties
+            .iter()
+            .map(|property| match property {
+                ObjectPropertyKind::ObjectProperty(property) => Some(property),
+                ObjectPropertyKind::SpreadProperty(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let [Some(a), Some(b), Some(c), Some(get), Some(set), Some(e), Some(f), None] =
+            properties[..]
+        else {
+            panic!()
+        };
+        assert!(a.shorthand && matches!(&a.value, Expression::Identifier(_)));
+        assert!(matches!(
+            &b.key,
+            PropertyKey::Computed(Expression::Identifier(_))
+        ));
+        let kinds = [c, get, set].map(|property| property.kind);
+        assert_eq!(
+            kinds,
+            [PropertyKind::Method, PropertyKind::Get, PropertyKind::Set]
+        );
+        let Expression::FunctionExpression(func) = &e.value else {
+            panic!()
+        };
+        assert!(func.is_async && !func.is_generator);
+        let Expression::FunctionExpression(func) = &f.value else {
+            panic!()
+        };
+        assert!(!func.is_async && func.is_generator);
+
+        // `get`, `set` and `async` are plain names unless another name follows
+        let program = Parser::new("x = { get, set: 1, async() {} };").parse();
+        assert!(program.is_ok());
+
+        // `{ a = 1 }` is only valid as a pattern
+        assert!(Parser::new("({ a = 1, b: { c = 2 } } = d);")
+            .parse()
+            .is_ok());
+        assert!(Parser::new("({ a = 1, ...b } = c);").parse().is_ok());
+        assert!(Parser::new("f = ({ a = 1 }) => a;").parse().is_ok());
+        assert!(Parser::new("for ({ a = 1 } of b);").parse().is_ok());
+        assert!(matches!(
+            Parser::new("x = { a = 1 };").parse(),
+            Err(ParseError::InvalidShorthandInitializer { span }) if span == Span::new(6, 11)
+        ));
+        assert!(Parser::new("f({ a = 1 });").parse().is_err());
+        assert!(Parser::new("({ ...a, b } = c);").parse().is_err());
+        assert!(Parser::new("({ a() {} } = c);").parse().is_err());
+        assert!(Parser::new("x = { a b };").parse().is_err());
+        assert!(Parser::new("x = { 'a' };").parse().is_err());
+    }
+
     #[test]
     fn parse_static_member_expression_test() {
         let source = "a.b.c";
@@ -389,6 +1116,18 @@ mod test {
                 panic!()
             }
         }
+
+        // Arguments are separated by commas
+        assert_eq!(
+            Parser::new("f(a b)").parse_expression().unwrap_err(),
+            ParseError::ExpectedToken {
+                span: Span::new(4, 5),
+                expected: Type::Comma,
+                found: Type::Identifier
+            }
+        );
+        assert!(Parser::new("f(a, b,)").parse_expression().is_ok());
+        assert!(Parser::new("f(a,, b)").parse_expression().is_err());
     }
 
     #[test]
@@ -406,4 +1145,788 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn parse_conditional_expression_test() {
+        let source = "a ? b : c ? d : e";
+        let parser = Parser::new(source);
+        let Expression::ConditionalExpression(expr) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        assert_eq!(expr.span, Span::new(0, 17));
+        assert!(matches!(expr.test, Expression::Identifier(_)));
+        assert!(matches!(
+            expr.alternate,
+            Expression::ConditionalExpression(_)
+        ));
+
+        let source = "x = f(a || b ? c = 1 : d, e) ? g : h";
+        let parser = Parser::new(source);
+        let Expression::AssignmentExpression(expr) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        let Expression::ConditionalExpression(right) = &expr.right else {
+            panic!()
+        };
+        let Expression::CallExpression(call) = &right.test else {
+            panic!()
+        };
+        assert_eq!(call.arguments.len(), 2);
+        assert!(matches!(
+            call.arguments[0],
+            Argument::Expression(Expression::ConditionalExpression(_))
+        ));
+
+        let error = Parser::new("a ? b c").parse_expression().unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::ExpectedToken {
+                expected: Type::Colon,
+                ..
+            }
+        ));
+        // the alternate is an AssignmentExpression, not an assignment to the conditional
+        let expr = Parser::new("a ? b : c = d").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::ConditionalExpression(expr)
+                if matches!(expr.alternate, Expression::AssignmentExpression(_))
+        ));
+    }
+
+    #[test]
+    fn parse_update_expression_test() {
+        let source = "for (i = 0; i < n; i++) --a.b[c];";
+        let mut parser = Parser::new(source);
+        let program = parser.parse().unwrap();
+        let Statement::ForStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Some(Expression::UpdateExpression(update)) = &stmt.update else {
+            panic!()
+        };
+        assert!(!update.prefix && update.operator == syntax::operator::UpdateOperator::Increment);
+        assert_eq!(update.span, Span::new(19, 22));
+        let Statement::ExpressionStatement(stmt) = &stmt.body else {
+            panic!()
+        };
+        let Expression::UpdateExpression(update) = &stmt.expression else {
+            panic!()
+        };
+        assert!(update.prefix && update.operator == syntax::operator::UpdateOperator::Decrement);
+        assert!(matches!(
+            update.argument,
+            AssignmentTarget::ComputedMemberExpression(_)
+        ));
+
+        // [no LineTerminator here] before a postfix operator
+        let mut parser = Parser::new("a\n++b");
+        let program = parser.parse().unwrap();
+        assert_eq!(program.body.len(), 2);
+
+        // A parenthesized identifier or member expression is a simple target
+        for source in ["(a)++", "++(a)", "(a.b)--", "--((a.b))"] {
+            let expr = Parser::new(source).parse_expression().unwrap();
+            let Expression::UpdateExpression(update) = expr else {
+                panic!("{source}")
+            };
+            assert!(matches!(
+                update.argument,
+                AssignmentTarget::Identifier(_) | AssignmentTarget::StaticMemberExpression(_)
+            ));
+        }
+
+        for (source, span) in [
+            ("++a++", Span::new(2, 5)),
+            ("f()--", Span::new(0, 3)),
+            ("(a + b)++", Span::new(0, 7)),
+        ] {
+            let error = Parser::new(source).parse_expression().unwrap_err();
+            assert_eq!(
+                error,
+                ParseError::InvalidAssignmentTarget { span },
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_try_statement_test() {
+        let source = "try { a } catch (e) { throw e } finally {}\ntry {} catch {}";
+        let mut parser = Parser::new(source);
+        let program = parser.parse().unwrap();
+        let Statement::TryStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let handler = stmt.handler.as_ref().unwrap();
+        assert!(matches!(
+            handler.param.as_ref().unwrap(),
+            BindingPattern::BindingIdentifier(ident) if ident.name == "e"
+        ));
+        assert!(matches!(handler.body.body[0], Statement::ThrowStatement(_)));
+        assert!(stmt.finalizer.is_some());
+        assert_eq!(stmt.span, Span::new(0, 42));
+        let Statement::TryStatement(stmt) = &program.body[1] else {
+            panic!()
+        };
+        assert!(stmt.handler.as_ref().unwrap().param.is_none());
+        assert!(stmt.finalizer.is_none());
+
+        let error = Parser::new("try {} a").parse().unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::ExpectedToken {
+                expected: Type::Catch,
+                ..
+            }
+        ));
+        // [no LineTerminator here] after `throw`
+        let error = Parser::new("throw\na").parse().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::NewlineAfterThrow {
+                span: Span::new(0, 5)
+            }
+        );
+    }
+
+    #[test]
+    fn parse_new_expression_test() {
+        let source = "new a.b.C(1)(2).d";
+        let parser = Parser::new(source);
+        let Expression::StaticMemberExpression(member) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        let Expression::CallExpression(call) = &member.object else {
+            panic!()
+        };
+        let Expression::NewExpression(new) = &call.callee else {
+            panic!()
+        };
+        assert_eq!(new.span, Span::new(0, 12));
+        assert_eq!(new.arguments.len(), 1);
+        assert!(matches!(new.callee, Expression::StaticMemberExpression(_)));
+
+        let parser = Parser::new("new new C()");
+        let Expression::NewExpression(new) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        assert!(new.arguments.is_empty());
+        assert!(matches!(new.callee, Expression::NewExpression(_)));
+
+        let mut parser = Parser::new("function f() { new.target === this }");
+        let program = parser.parse().unwrap();
+        let Statement::FunctionDeclaration(func) = &program.body[0] else {
+            panic!()
+        };
+        let Statement::ExpressionStatement(stmt) = &func.body.as_ref().unwrap().statements[0]
+        else {
+            panic!()
+        };
+        let Expression::BinaryExpression(expr) = &stmt.expression else {
+            panic!()
+        };
+        assert!(
+            matches!(&expr.left, Expression::MetaProperty(meta) if meta.property.name == "target")
+        );
+        assert!(matches!(expr.right, Expression::ThisExpression(_)));
+
+        let error = Parser::new("new.foo").parse_expression().unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::ExpectedToken {
+                expected: Type::Target,
+                ..
+            }
+        ));
+
+        // Arrow functions see `new.target` of the enclosing function
+        for source in [
+            "function f() { () => new.target; }",
+            "class A { x = new.target; static { new.target; } m() { new.target; } }",
+        ] {
+            assert!(Parser::new(source).parse().is_ok(), "{source}");
+        }
+        for (source, span) in [
+            ("new.target;", Span::new(0, 10)),
+            ("x => new.target;", Span::new(5, 15)),
+        ] {
+            assert_eq!(
+                Parser::new(source).parse().unwrap_err(),
+                ParseError::NewTargetOutsideFunction { span },
+                "{source}"
+            );
+        }
+        let error = Parser::new("new.target").parse_module().unwrap_err();
+        assert!(matches!(error, ParseError::NewTargetOutsideFunction { .. }));
+    }
+
+    #[test]
+    fn parse_arrow_function_test() {
+        let parser = Parser::new("a => b => a");
+        let Expression::ArrowFunctionExpression(arrow) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        assert!(arrow.expression);
+        assert_eq!(arrow.span, Span::new(0, 11));
+        assert!(matches!(
+            &arrow.params.params[0],
+            BindingPattern::BindingIdentifier(ident) if ident.name == "a"
+        ));
+        let Statement::ExpressionStatement(stmt) = &arrow.body.statements[0] else {
+            panic!()
+        };
+        assert!(matches!(
+            stmt.expression,
+            Expression::ArrowFunctionExpression(_)
+        ));
+
+        let parser = Parser::new("(a, b) => { return a }");
+        let Expression::ArrowFunctionExpression(arrow) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        assert!(!arrow.expression);
+        assert_eq!(arrow.params.params.len(), 2);
+        assert!(matches!(
+            arrow.body.statements[0],
+            Statement::ReturnStatement(_)
+        ));
+
+        let parser = Parser::new("() => ({})");
+        let Expression::ArrowFunctionExpression(arrow) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        assert!(arrow.params.params.is_empty());
+
+        let parser = Parser::new("f = function g(a) {}");
+        let Expression::AssignmentExpression(assign) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        assert!(
+            matches!(&assign.right, Expression::FunctionExpression(func) if func.id.as_ref().unwrap().name == "g")
+        );
+
+        let error = Parser::new("(a + b) => 1").parse_expression().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::InvalidArrowParameters {
+                span: Span::new(0, 7)
+            }
+        );
+        // [no LineTerminator here] before `=>`
+        assert!(Parser::new("(a)\n=> 1").parse().is_err());
+        // Arrow functions, methods, class code and parameters that are not all plain names
+        // never bind a name twice
+        for (source, span) in [
+            ("(a, a) => 1", Span::new(4, 5)),
+            ("x = ({ b: [a] }, ...a) => 1", Span::new(20, 21)),
+            ("({ m(a, a) {} })", Span::new(8, 9)),
+            ("class A { m(a, b = 1, { c: a }) {} }", Span::new(27, 28)),
+            ("class A { m() { function f(a, a) {} } }", Span::new(30, 31)),
+            ("function f(a, [a]) {}", Span::new(15, 16)),
+        ] {
+            assert_eq!(
+                Parser::new(source).parse().unwrap_err(),
+                ParseError::DuplicateParameter { span },
+                "{source}"
+            );
+        }
+        assert!(Parser::new("function f(a, a) {}").parse().is_ok());
+        assert!(Parser::new("()").parse_expression().is_err());
+        // Parameters are separated by commas
+        assert_eq!(
+            Parser::new("(a b) => 1").parse_expression().unwrap_err(),
+            ParseError::ExpectedToken {
+                span: Span::new(3, 4),
+                expected: Type::Comma,
+                found: Type::Identifier
+            }
+        );
+        assert!(Parser::new("(a, b,) => 1").parse_expression().is_ok());
+    }
+
+    #[test]
+    fn parse_class_test() {
+        let source = "class A extends B.C { static x = 1; y\n get; static get z() {} set z(v) {} constructor() { super(1) } static {} }";
+        let mut parser = Parser::new(source);
+        let program = parser.parse().unwrap();
+        let Statement::ClassDeclaration(class) = &program.body[0] else {
+            panic!()
+        };
+        assert_eq!(class.id.as_ref().unwrap().name, "A");
+        assert_eq!(class.span, Span::new(0, source.len()));
+        assert!(matches!(
+            class.super_class,
+            Some(Expression::StaticMemberExpression(_))
+        ));
+        let elements = &class.body.body;
+        assert_eq!(elements.len(), 7);
+        let ClassElement::PropertyDefinition(x) = &elements[0] else {
+            panic!()
+        };
+        assert!(x.r#static && x.value.is_some());
+        // `get` followed by `;` is a field named `get`
+        let ClassElement::PropertyDefinition(get) = &elements[2] else {
+            panic!()
+        };
+        assert!(!get.r#static && get.value.is_none());
+        assert!(matches!(&get.key, PropertyKey::IdentifierName(key) if key.name == "get"));
+        let ClassElement::MethodDefinition(z) = &elements[3] else {
+            panic!()
+        };
+        assert!(z.r#static && z.kind == MethodDefinitionKind::Get);
+        let ClassElement::MethodDefinition(z) = &elements[4] else {
+            panic!()
+        };
+        assert!(!z.r#static && z.kind == MethodDefinitionKind::Set);
+        let ClassElement::MethodDefinition(constructor) = &elements[5] else {
+            panic!()
+        };
+        assert_eq!(constructor.kind, MethodDefinitionKind::Constructor);
+        let Statement::ExpressionStatement(stmt) =
+            &constructor.value.body.as_ref().unwrap().statements[0]
+        else {
+            panic!()
+        };
+        assert!(
+            matches!(&stmt.expression, Expression::CallExpression(call) if matches!(call.callee, Expression::Super(_)))
+        );
+        assert!(matches!(elements[6], ClassElement::StaticBlock(_)));
+
+        let parser = Parser::new("x = class { static() {} }");
+        let Expression::AssignmentExpression(assign) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        let Expression::ClassExpression(class) = &assign.right else {
+            panic!()
+        };
+        assert!(class.id.is_none());
+        assert!(
+            matches!(&class.body.body[0], ClassElement::MethodDefinition(method) if !method.r#static)
+        );
+
+        // `get`, `set` and `static` take the name on the next line, `async` does not
+        let source = "class A { get\n x() {} set\n x(v) {} static\n x() {} async\n x() {} }";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::ClassDeclaration(class) = &program.body[0] else {
+            panic!()
+        };
+        let elements = &class.body.body;
+        assert_eq!(elements.len(), 5);
+        for (element, r#static, kind) in [
+            (&elements[0], false, MethodDefinitionKind::Get),
+            (&elements[1], false, MethodDefinitionKind::Set),
+            (&elements[2], true, MethodDefinitionKind::Method),
+            (&elements[4], false, MethodDefinitionKind::Method),
+        ] {
+            let ClassElement::MethodDefinition(method) = element else {
+                panic!()
+            };
+            assert!(matches!(&method.key, PropertyKey::IdentifierName(key) if key.name == "x"));
+            assert_eq!((method.r#static, method.kind), (r#static, kind));
+            assert!(!method.value.is_async);
+        }
+        assert!(
+            matches!(&elements[3], ClassElement::PropertyDefinition(field) if matches!(&field.key, PropertyKey::IdentifierName(key) if key.name == "async"))
+        );
+
+        // One constructor per class, a static method or a computed key is not one
+        let error = Parser::new("class A { constructor() {} 'constructor'() {} }")
+            .parse()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::DuplicateConstructor {
+                span: Span::new(27, 45)
+            }
+        );
+        assert_eq!(error.code(), "E0025");
+        assert!(Parser::new(
+            "class A { constructor() {} static constructor() {} ['constructor']() {} }"
+        )
+        .parse()
+        .is_ok());
+        assert!(Parser::new("class {}").parse().is_err());
+        assert!(Parser::new("x = super").parse_expression().is_err());
+    }
+
+    #[test]
+    fn parse_async_generator_test() {
+        let source = "async function* f() { yield* await a; yield\nb; }";
+        let program = Parser::new(source).parse().unwrap();
+        let Statement::FunctionDeclaration(func) = &program.body[0] else {
+            panic!()
+        };
+        assert!(func.is_async && func.is_generator);
+        let statements = &func.body.as_ref().unwrap().statements;
+        let Statement::ExpressionStatement(stmt) = &statements[0] else {
+            panic!()
+        };
+        let Expression::YieldExpression(expr) = &stmt.expression else {
+            panic!()
+        };
+        assert!(expr.delegate);
+        assert!(matches!(
+            expr.argument,
+            Some(Expression::AwaitExpression(_))
+        ));
+        // `yield` takes no argument on the next line
+        let Statement::ExpressionStatement(stmt) = &statements[1] else {
+            panic!()
+        };
+        assert!(
+            matches!(&stmt.expression, Expression::YieldExpression(expr) if expr.argument.is_none())
+        );
+
+        let parser = Parser::new("x = async (a, b) => await a");
+        let Expression::AssignmentExpression(assign) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        let Expression::ArrowFunctionExpression(arrow) = &assign.right else {
+            panic!()
+        };
+        assert!(arrow.is_async && arrow.params.params.len() == 2);
+        let source =
+            "x = { async *a() { yield 1 } }; class A { async b() {} *c() {} static async *d() {} }";
+        assert!(Parser::new(source).parse().is_ok());
+
+        // `await` and `yield` are identifiers outside async functions and generators of scripts
+        assert!(Parser::new("var await, yield; await(yield);")
+            .parse()
+            .is_ok());
+        assert!(Parser::new("function* f() { var yield; }").parse().is_err());
+        assert!(
+            Parser::new("async function f() { function g() { await; } }")
+                .parse()
+                .is_ok()
+        );
+        assert!(Parser::new("async function f() { () => await a; }")
+            .parse()
+            .is_err());
+        assert!(Parser::new("await a;").parse_module().is_ok());
+        assert!(Parser::new("var await;").parse_module().is_err());
+        assert!(matches!(
+            Parser::new("async function f() { await a ** 2; }").parse(),
+            Err(ParseError::UnaryBeforeExponentiation { .. })
+        ));
+
+        // `async` is an ordinary name unless a function or arrow function follows
+        let program = Parser::new("async(1, ...a).b; async();").parse().unwrap();
+        let Statement::ExpressionStatement(stmt) = &program.body[0] else {
+            panic!()
+        };
+        let Expression::StaticMemberExpression(member) = &stmt.expression else {
+            panic!()
+        };
+        assert!(matches!(
+            &member.object,
+            Expression::CallExpression(call)
+                if call.arguments.len() == 2
+                    && matches!(&call.callee, Expression::Identifier(ident) if ident.name == "async")
+        ));
+        for source in [
+            "async(1, 2);",
+            "var async = 1;",
+            "async;",
+            "x = async => async;",
+            "let { async } = o; [async] = p;",
+        ] {
+            assert!(Parser::new(source).parse().is_ok(), "{source}");
+        }
+        let expr = Parser::new("async (a, ...b) => await a")
+            .parse_expression()
+            .unwrap();
+        let Expression::ArrowFunctionExpression(arrow) = expr else {
+            panic!()
+        };
+        assert!(arrow.is_async && arrow.params.params.len() == 1 && arrow.params.rest.is_some());
+        assert!(Parser::new("async (a, ...b, c) => a").parse().is_err());
+        assert!(Parser::new("async (a)(b) => a").parse().is_err());
+        // [no LineTerminator here] after `async`
+        assert!(Parser::new("async\n(a) => a").parse().is_err());
+        // [no LineTerminator here] before `=>`
+        assert_eq!(
+            Parser::new("async x\n=> x").parse().unwrap_err(),
+            ParseError::UnexpectedToken {
+                span: Span::new(8, 10),
+                found: Type::Arrow
+            }
+        );
+        // The parameters of an async arrow function never contain `await`
+        for (source, span) in [
+            ("async (a, await) => 1", Span::new(10, 15)),
+            (
+                "async function f() { async (a = await b) => a; }",
+                Span::new(32, 39),
+            ),
+        ] {
+            assert_eq!(
+                Parser::new(source).parse().unwrap_err(),
+                ParseError::UnexpectedToken {
+                    span,
+                    found: Type::Await
+                },
+                "{source}"
+            );
+        }
+        assert!(Parser::new("await; async (a) => await a").parse().is_ok());
+    }
+
+    #[test]
+    fn parse_module_test() {
+        let source =
+            "import a, { b as c } from 'd';\nexport default function () {}\nexport { c as e };";
+        let mut parser = Parser::new(source);
+        let program = parser.parse_module().unwrap();
+        assert_eq!(program.source_type, SourceType::Module);
+        let Statement::ImportDeclaration(import) = &program.body[0] else {
+            panic!()
+        };
+        assert_eq!(import.source.value, "'d'");
+        assert_eq!(import.span, Span::new(0, 30));
+        assert!(matches!(
+            &import.specifiers[0],
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) if specifier.local.name == "a"
+        ));
+        let ImportDeclarationSpecifier::ImportSpecifier(specifier) = &import.specifiers[1] else {
+            panic!()
+        };
+        assert!(
+            matches!(&specifier.imported, ModuleExportName::IdentifierName(name) if name.name == "b")
+        );
+        assert_eq!(specifier.local.name, "c");
+        let Statement::ExportDefaultDeclaration(export) = &program.body[1] else {
+            panic!()
+        };
+        assert!(matches!(
+            &export.declaration,
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) if func.id.is_none()
+        ));
+        let Statement::ExportNamedDeclaration(export) = &program.body[2] else {
+            panic!()
+        };
+        assert!(export.declaration.is_none() && export.source.is_none());
+        assert!(
+            matches!(&export.specifiers[0].exported, ModuleExportName::IdentifierName(name) if name.name == "e")
+        );
+
+        let parser = Parser::new("import(a).then(f)");
+        let Expression::CallExpression(call) = parser.parse_expression().unwrap() else {
+            panic!()
+        };
+        let Expression::StaticMemberExpression(member) = &call.callee else {
+            panic!()
+        };
+        assert!(matches!(member.object, Expression::ImportExpression(_)));
+
+        // dynamic import is allowed in scripts, the declarations are not
+        let program = Parser::new("import('a')").parse().unwrap();
+        assert_eq!(program.source_type, SourceType::Script);
+        assert_eq!(
+            Parser::new("export var a").parse().unwrap_err(),
+            ParseError::ModuleSyntaxOutsideModule {
+                span: Span::new(0, 6)
+            }
+        );
+        assert_eq!(
+            Parser::new("import.meta").parse().unwrap_err(),
+            ParseError::ModuleSyntaxOutsideModule {
+                span: Span::new(0, 11)
+            }
+        );
+        assert_eq!(
+            Parser::new("{ import 'a' }").parse_module().unwrap_err(),
+            ParseError::ModuleDeclarationNotTopLevel {
+                span: Span::new(2, 8)
+            }
+        );
+        assert!(Parser::new("import a, 'b'").parse_module().is_err());
+        assert!(Parser::new("import { default } from 'b'")
+            .parse_module()
+            .is_err());
+        // only `export default` may omit the name of a declaration
+        for source in [
+            "export function () {}",
+            "export function* () {}",
+            "export class {}",
+        ] {
+            assert!(Parser::new(source).parse_module().is_err(), "{source}");
+        }
+        // without `from`, only local names can be exported
+        for (source, span) in [
+            ("export { 'a' };", Span::new(9, 12)),
+            ("export { a, default as b };", Span::new(12, 19)),
+        ] {
+            let error = Parser::new(source).parse_module().unwrap_err();
+            assert!(
+                matches!(error, ParseError::UnexpectedToken { span: s, .. } if s == span),
+                "{source}"
+            );
+        }
+        assert!(Parser::new("export { 'a', default } from 'b';")
+            .parse_module()
+            .is_ok());
+
+        // modules are strict mode code
+        for (source, error) in [
+            (
+                "function f(a, a) {}",
+                ParseError::DuplicateParameter {
+                    span: Span::new(14, 15),
+                },
+            ),
+            (
+                "x = function (c, b, c) {}",
+                ParseError::DuplicateParameter {
+                    span: Span::new(20, 21),
+                },
+            ),
+            (
+                "delete x;",
+                ParseError::DeleteOfIdentifier {
+                    span: Span::new(0, 8),
+                },
+            ),
+            (
+                "delete (x);",
+                ParseError::DeleteOfIdentifier {
+                    span: Span::new(0, 10),
+                },
+            ),
+        ] {
+            assert_eq!(Parser::new(source).parse_module().unwrap_err(), error);
+            assert!(Parser::new(source).parse().is_ok(), "{source}");
+        }
+        assert!(Parser::new("delete x.y; function f(a, b) {}")
+            .parse_module()
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_error_span_test() {
+        let source = "a;\nif (a ;";
+        let mut parser = Parser::new(source);
+        let ret = parser.parse();
+        let error = ret.unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::ExpectedToken {
+                span: Span::new(9, 10),
+                expected: Type::RParen,
+                found: Type::Semicolon,
+            }
+        );
+        assert_eq!(error.code(), "E0002");
+        assert!(error.render(source).contains("2 | if (a ;\n  |       ^"));
+    }
+
+    #[test]
+    fn parse_invalid_assignment_target_test() {
+        let source = "a + b = c";
+        let parser = Parser::new(source);
+        let ret = parser.parse_expression();
+        assert_eq!(
+            ret.unwrap_err(),
+            ParseError::InvalidAssignmentTarget {
+                span: Span::new(0, 5)
+            }
+        );
+    }
+
+    #[test]
+    fn parse_recovering_test() {
+        let source = "a = ;\nfunction f() { b = ); c; }\n}\nif (d) e;";
+        let parser = Parser::new(source);
+        let ParseResult {
+            program,
+            diagnostics,
+        } = parser.parse_recovering();
+        assert_eq!(diagnostics.len(), 3);
+        assert!(matches!(
+            diagnostics[0],
+            ParseError::UnexpectedToken {
+                found: Type::Semicolon,
+                ..
+            }
+        ));
+        assert_eq!(program.body.len(), 4);
+        assert!(matches!(&program.body[0], Statement::Error(s) if s.span == Span::new(0, 5)));
+        let Statement::FunctionDeclaration(func) = &program.body[1] else {
+            panic!()
+        };
+        let body = &func.body.as_ref().unwrap().statements;
+        assert!(matches!(body[0], Statement::Error(_)));
+        assert!(matches!(body[1], Statement::ExpressionStatement(_)));
+        assert!(matches!(program.body[2], Statement::Error(_)));
+        assert!(matches!(program.body[3], Statement::IfStatement(_)));
+    }
+
+    #[test]
+    fn parse_lex_error_test() {
+        let source = "a = 1;\nb = 'c";
+        let error = Parser::new(source).parse().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::Lex {
+                span: Span::new(11, 13),
+                kind: js_lexer::error::LexErrorKind::UnterminatedString,
+            }
+        );
+        assert_eq!(
+            error.render(source),
+            "error[E0007]: Unterminated string literal\n --> 2:5\n  |\n2 | b = 'c\n  |     ^^\n"
+        );
+
+        let ParseResult { diagnostics, .. } = Parser::new("f(a # b);\nc;").parse_recovering();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].to_string(), "Unexpected character `#`");
+    }
+
+    /// ECMAScript 12.10 Automatic Semicolon Insertion
+    #[test]
+    fn automatic_semicolon_insertion_test() {
+        use crate::codegen::js::{JsCodegen, JsCodegenOptions};
+
+        let accepted = [
+            ("a\nb", "a;\nb;\n"),
+            ("{ 1\n2 } 3", "{\n  1;\n  2;\n}\n3;\n"),
+            ("var a = 1\nvar b = 2", "var a = 1;\nvar b = 2;\n"),
+            ("a\n/* \n */ b", "a;\nb;\n"),
+            ("do {} while (a) b", "do {} while (a);\nb;\n"),
+            // no semicolon is inserted when the next line continues the statement
+            ("a = b\n(c)", "a = b(c);\n"),
+            ("a = b\n+c", "a = b + c;\n"),
+            ("a\n.b\n[c]", "a.b[c];\n"),
+            // restricted productions
+            (
+                "function f() {\n  return\n  a + b\n}",
+                "function f() {\n  return;\n  a + b;\n}\n",
+            ),
+            ("for (;;) { break\na }", "for (;;) {\n  break;\n  a;\n}\n"),
+            (
+                "while (a) { continue\nb }",
+                "while (a) {\n  continue;\n  b;\n}\n",
+            ),
+        ];
+        for (source, expected) in accepted {
+            let program = Parser::new(source).parse().unwrap();
+            let code = JsCodegen::new(JsCodegenOptions::default()).build(&program);
+            assert_eq!(code, expected, "{source:?}");
+        }
+
+        let rejected = [
+            ("a b", 1),
+            ("a /* */ b", 1),
+            ("{ 1 2 } 3", 3),
+            ("if (a) b c", 8),
+            ("var a = 1 var b", 9),
+        ];
+        for (source, offset) in rejected {
+            let error = Parser::new(source).parse().unwrap_err();
+            let span = Span::new(offset, offset);
+            assert_eq!(error, ParseError::MissingSemicolon { span }, "{source:?}");
+        }
+        // semicolons are never inserted in the head of a `for` statement
+        assert!(Parser::new("for (a\nb\n) {}").parse().is_err());
+    }
 }
@@ -0,0 +1,330 @@
+use super::*;
+use crate::ast::*;
+use crate::syntax::operator::AssignmentOperator;
+
+impl<'a> Parser<'a> {
+    /// Section 14.3.3 Destructuring Binding Patterns
+    pub(crate) fn parse_binding_pattern(&mut self) -> Result<BindingPattern<'a>> {
+        match self.cur_kind() {
+            Type::LCurly => self.parse_object_pattern(),
+            Type::LBrack => self.parse_array_pattern(),
+            _ => {
+                let ident = self.parse_identifier()?;
+                Ok(BindingPattern::BindingIdentifier(Box::new(ident)))
+            }
+        }
+    }
+
+    /// A binding pattern with an optional default value
+    pub(crate) fn parse_binding_element(&mut self) -> Result<BindingPattern<'a>> {
+        let span = self.start_span();
+        let pattern = self.parse_binding_pattern()?;
+        self.parse_binding_default(span, pattern)
+    }
+
+    fn parse_binding_default(
+        &mut self,
+        span: Span,
+        left: BindingPattern<'a>,
+    ) -> Result<BindingPattern<'a>> {
+        if !self.eat(Type::Eq) {
+            return Ok(left);
+        }
+        let right = self.context(Context::In, Context::empty(), |p| {
+            p.parse_assignment_expression_or_higher()
+        })?;
+        Ok(BindingPattern::AssignmentPattern(Box::new(
+            AssignmentPattern {
+                span: self.end_span(span),
+                left,
+                right,
+            },
+        )))
+    }
+
+    fn parse_object_pattern(&mut self) -> Result<BindingPattern<'a>> {
+        let span = self.start_span();
+        self.bump_any(); // bump `{`
+        let mut properties = vec![];
+        let mut rest = None;
+        while !self.at(Type::RCurly) {
+            if self.at(Type::Dot3) {
+                // The rest of an object can only be bound to a name, and comes last
+                let rest_span = self.start_span();
+                self.bump_any(); // bump `...`
+                let ident = self.parse_identifier()?;
+                rest = Some(Box::new(RestElement {
+                    span: self.end_span(rest_span),
+                    argument: BindingPattern::BindingIdentifier(Box::new(ident)),
+                }));
+                break;
+            }
+            properties.push(self.parse_binding_property()?);
+            if !self.at(Type::RCurly) {
+                self.expect(Type::Comma)?;
+            }
+        }
+        self.expect(Type::RCurly)?;
+        Ok(BindingPattern::ObjectPattern(Box::new(ObjectPattern {
+            span: self.end_span(span),
+            properties,
+            rest,
+        })))
+    }
+
+    fn parse_binding_property(&mut self) -> Result<BindingProperty<'a>> {
+        let span = self.start_span();
+        let is_identifier = matches!(self.cur_kind(), Type::Identifier | Type::Async);
+        let key = self.parse_property_key()?;
+        let shorthand = !self.eat(Type::Colon);
+        let value = match &key {
+            // `{ a }` or `{ a = 1 }`
+            PropertyKey::IdentifierName(name) if shorthand && is_identifier => {
+                let ident = Identifier {
+                    span: name.span,
+                    name: name.name,
+                };
+                let value = BindingPattern::BindingIdentifier(Box::new(ident));
+                self.parse_binding_default(span, value)?
+            }
+            _ if shorthand => {
+                return Err(ParseError::ExpectedToken {
+                    span: self.cur_token_span(),
+                    expected: Type::Colon,
+                    found: self.cur_kind(),
+                })
+            }
+            _ => self.parse_binding_element()?,
+        };
+        Ok(BindingProperty {
+            span: self.end_span(span),
+            key,
+            value,
+            shorthand,
+        })
+    }
+
+    fn parse_array_pattern(&mut self) -> Result<BindingPattern<'a>> {
+        let span = self.start_span();
+        self.bump_any(); // bump `[`
+        let mut elements = vec![];
+        let mut rest = None;
+        while !self.at(Type::RBrack) {
+            if self.eat(Type::Comma) {
+                elements.push(None);
+                continue;
+            }
+            if self.at(Type::Dot3) {
+                // The rest element comes last, without a default or a trailing comma
+                let rest_span = self.start_span();
+                self.bump_any(); // bump `...`
+                let argument = self.parse_binding_pattern()?;
+                rest = Some(Box::new(RestElement {
+                    span: self.end_span(rest_span),
+                    argument,
+                }));
+                break;
+            }
+            elements.push(Some(self.parse_binding_element()?));
+            if !self.at(Type::RBrack) {
+                self.expect(Type::Comma)?;
+            }
+        }
+        self.expect(Type::RBrack)?;
+        Ok(BindingPattern::ArrayPattern(Box::new(ArrayPattern {
+            span: self.end_span(span),
+            elements,
+            rest,
+        })))
+    }
+}
+
+/// Reinterpret an arrow function parameter, parsed as an expression, as a binding pattern
+pub(crate) fn binding_pattern_from_expression(expr: Expression) -> Option<BindingPattern> {
+    match expr {
+        Expression::Identifier(ident) => Some(BindingPattern::BindingIdentifier(ident)),
+        Expression::ArrayExpression(array) => {
+            let mut elements = vec![];
+            let mut rest = None;
+            let mut iter = array.elements.into_iter().peekable();
+            while let Some(element) = iter.next() {
+                match element {
+                    ArrayExpressionElement::Elision(_) => elements.push(None),
+                    ArrayExpressionElement::Expression(expr) => {
+                        elements.push(Some(binding_pattern_from_expression(expr)?))
+                    }
+                    ArrayExpressionElement::SpreadElement(spread) if iter.peek().is_none() => {
+                        rest = Some(Box::new(RestElement {
+                            span: spread.span,
+                            argument: binding_pattern_from_expression(spread.argument)?,
+                        }));
+                    }
+                    ArrayExpressionElement::SpreadElement(_) => return None,
+                }
+            }
+            // A rest element is never followed by a comma
+            if rest.is_some() && array.trailing_comma.is_some() {
+                return None;
+            }
+            Some(BindingPattern::ArrayPattern(Box::new(ArrayPattern {
+                span: array.span,
+                elements,
+                rest,
+            })))
+        }
+        Expression::ObjectExpression(object) => {
+            let mut properties = vec![];
+            let mut rest = None;
+            let mut iter = object.properties.into_iter().peekable();
+            while let Some(property) = iter.next() {
+                match property {
+                    ObjectPropertyKind::ObjectProperty(property)
+                        if property.kind == PropertyKind::Init =>
+                    {
+                        properties.push(BindingProperty {
+                            span: property.span,
+                            key: property.key,
+                            value: binding_pattern_from_expression(property.value)?,
+                            shorthand: property.shorthand,
+                        });
+                    }
+                    // The rest of an object can only be bound to a name, and comes last
+                    ObjectPropertyKind::SpreadProperty(spread) if iter.peek().is_none() => {
+                        let Expression::Identifier(ident) = spread.argument else {
+                            return None;
+                        };
+                        rest = Some(Box::new(RestElement {
+                            span: spread.span,
+                            argument: BindingPattern::BindingIdentifier(ident),
+                        }));
+                    }
+                    _ => return None,
+                }
+            }
+            if rest.is_some() && object.trailing_comma.is_some() {
+                return None;
+            }
+            Some(BindingPattern::ObjectPattern(Box::new(ObjectPattern {
+                span: object.span,
+                properties,
+                rest,
+            })))
+        }
+        // The left side has already been reinterpreted as an assignment target
+        Expression::AssignmentExpression(assign)
+            if assign.operator == AssignmentOperator::Assign =>
+        {
+            Some(BindingPattern::AssignmentPattern(Box::new(
+                AssignmentPattern {
+                    span: assign.span,
+                    left: binding_pattern_from_target(assign.left)?,
+                    right: assign.right,
+                },
+            )))
+        }
+        _ => None,
+    }
+}
+
+fn binding_pattern_from_target(target: AssignmentTarget) -> Option<BindingPattern> {
+    match target {
+        AssignmentTarget::Identifier(ident) => Some(BindingPattern::BindingIdentifier(ident)),
+        AssignmentTarget::ArrayAssignmentTarget(array) => {
+            let elements = array
+                .elements
+                .into_iter()
+                .map(|element| match element {
+                    None => Some(None),
+                    Some(element) => binding_pattern_from_maybe_default(element).map(Some),
+                })
+                .collect::<Option<_>>()?;
+            let rest = match array.rest {
+                Some(rest) => Some(Box::new(RestElement {
+                    span: rest.span,
+                    argument: binding_pattern_from_target(rest.target)?,
+                })),
+                None => None,
+            };
+            Some(BindingPattern::ArrayPattern(Box::new(ArrayPattern {
+                span: array.span,
+                elements,
+                rest,
+            })))
+        }
+        AssignmentTarget::ObjectAssignmentTarget(object) => {
+            let properties = object
+                .properties
+                .into_iter()
+                .map(|property| {
+                    Some(BindingProperty {
+                        span: property.span,
+                        key: property.key,
+                        value: binding_pattern_from_maybe_default(property.value)?,
+                        shorthand: false,
+                    })
+                })
+                .collect::<Option<_>>()?;
+            let rest = match object.rest {
+                Some(rest) => match rest.target {
+                    AssignmentTarget::Identifier(ident) => Some(Box::new(RestElement {
+                        span: rest.span,
+                        argument: BindingPattern::BindingIdentifier(ident),
+                    })),
+                    _ => return None,
+                },
+                None => None,
+            };
+            Some(BindingPattern::ObjectPattern(Box::new(ObjectPattern {
+                span: object.span,
+                properties,
+                rest,
+            })))
+        }
+        AssignmentTarget::StaticMemberExpression(_)
+        | AssignmentTarget::ComputedMemberExpression(_) => None,
+    }
+}
+
+fn binding_pattern_from_maybe_default(
+    target: AssignmentTargetMaybeDefault,
+) -> Option<BindingPattern> {
+    match target {
+        AssignmentTargetMaybeDefault::AssignmentTarget(target) => {
+            binding_pattern_from_target(target)
+        }
+        AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => Some(
+            BindingPattern::AssignmentPattern(Box::new(AssignmentPattern {
+                span: target.span,
+                left: binding_pattern_from_target(target.binding)?,
+                right: target.init,
+            })),
+        ),
+    }
+}
+
+/// Collect the identifiers bound by `pattern` into `names`
+pub(crate) fn bound_names<'b, 'a>(
+    pattern: &'b BindingPattern<'a>,
+    names: &mut Vec<&'b Identifier<'a>>,
+) {
+    match pattern {
+        BindingPattern::BindingIdentifier(ident) => names.push(ident),
+        BindingPattern::ObjectPattern(object) => {
+            for property in &object.properties {
+                bound_names(&property.value, names);
+            }
+            if let Some(rest) = &object.rest {
+                bound_names(&rest.argument, names);
+            }
+        }
+        BindingPattern::ArrayPattern(array) => {
+            for element in array.elements.iter().flatten() {
+                bound_names(element, names);
+            }
+            if let Some(rest) = &array.rest {
+                bound_names(&rest.argument, names);
+            }
+        }
+        BindingPattern::AssignmentPattern(assign) => bound_names(&assign.left, names),
+    }
+}
@@ -5,17 +5,77 @@ use crate::ast::*;
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_statements(&mut self, is_top_level: bool) -> Result<Vec<Statement<'a>>> {
+        let stmt_ctx = match self.source_type {
+            SourceType::Module if is_top_level => StatementContext::ModuleItem,
+            _ => StatementContext::StatementList,
+        };
+        // A function body in the default value of an arrow function parameter may be parsed
+        // before the parameters are reinterpreted
+        let outer_covers = std::mem::take(&mut self.cover_initialized_names);
+        // Labels do not cross function boundaries
+        let outer_labels = std::mem::take(&mut self.labels);
         let mut statements = vec![];
         while !self.at(Type::EOF) {
             if !is_top_level && self.at(Type::RCurly) {
                 break;
             }
-            statements.push(self.parse_statement(StatementContext::StatementList)?);
+            statements.push(self.parse_statement_list_item(stmt_ctx)?);
         }
+        self.cover_initialized_names = outer_covers;
+        self.labels = outer_labels;
 
         Ok(statements)
     }
+
+    /// Parse a statement of a statement list, replacing it with [`Statement::Error`] and
+    /// resynchronizing on failure when in recovery mode
+    pub(crate) fn parse_statement_list_item(
+        &mut self,
+        stmt_ctx: StatementContext,
+    ) -> Result<Statement<'a>> {
+        let span = self.start_span();
+        match self.parse_statement(stmt_ctx) {
+            Ok(stmt) => Ok(stmt),
+            Err(error) if self.recover => {
+                self.errors.push(error);
+                self.synchronize(span.start);
+                Ok(Statement::Error(Box::new(ErrorStatement {
+                    span: self.end_span(span),
+                })))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Skip tokens until the next statement boundary: after a `;`, before a `}` or before a
+    /// keyword that starts a statement
+    fn synchronize(&mut self, start: usize) {
+        // Always make progress, the failing token may itself be a boundary
+        if self.cur_token().start == start && !self.cur_kind().is_eof() {
+            self.bump_any();
+        }
+        loop {
+            match self.cur_kind() {
+                Type::Semicolon => {
+                    self.bump_any();
+                    return;
+                }
+                Type::RCurly | Type::EOF => return,
+                kind if kind.is_statement_keyword() => return,
+                _ => self.bump_any(),
+            }
+        }
+    }
     pub(crate) fn parse_statement(&mut self, stmt_ctx: StatementContext) -> Result<Statement<'a>> {
+        let stmt = self.parse_statement_kind(stmt_ctx);
+        // `{ a = 1 }` has to be part of a pattern by the end of the statement
+        match self.cover_initialized_names.drain(..).next() {
+            Some(span) if stmt.is_ok() => Err(ParseError::InvalidShorthandInitializer { span }),
+            _ => stmt,
+        }
+    }
+
+    fn parse_statement_kind(&mut self, stmt_ctx: StatementContext) -> Result<Statement<'a>> {
         match self.cur_kind() {
             Type::LCurly => self.parse_block_statement(),
             Type::Semicolon => Ok(self.parse_empty_statement()),
@@ -26,7 +86,22 @@ impl<'a> Parser<'a> {
             Type::Break | Type::Continue => self.parse_break_or_continue_statement(),
             Type::Switch => self.parse_switch_statement(),
             Type::Return => self.parse_return_statement(),
+            Type::Throw => self.parse_throw_statement(),
+            Type::Try => self.parse_try_statement(),
             Type::Function => self.parse_function_declaration(stmt_ctx),
+            // [no LineTerminator here] after `async`
+            Type::Async if self.peek_kind() == Type::Function && !self.peek_is_on_new_line() => {
+                self.parse_function_declaration(stmt_ctx)
+            }
+            Type::Class => self.parse_class_declaration(stmt_ctx),
+            // `import(...)` and `import.meta` start expressions
+            Type::Import if !matches!(self.peek_kind(), Type::LParen | Type::Dot) => {
+                self.parse_import_declaration(stmt_ctx)
+            }
+            Type::Export => self.parse_export_declaration(stmt_ctx),
+            _ if self.at_identifier() && self.peek_kind() == Type::Colon => {
+                self.parse_labeled_statement()
+            }
             kind if kind.is_variable_declaration() => self.parse_variable_statement(stmt_ctx),
             _ => self.parse_expression_statement(),
         }
@@ -36,17 +111,68 @@ impl<'a> Parser<'a> {
         let start_span = self.start_span();
         let kind = self.cur_kind();
         self.bump_any();
+        // A label on the next line starts a new statement
+        let label = if self.at_identifier() && !self.cur_token().is_on_new_line {
+            let label = self.parse_identifier()?;
+            match self.labels.iter().find(|l| l.name == label.name) {
+                None => return Err(ParseError::UndefinedLabel { span: label.span }),
+                Some(l) if kind == Type::Continue && !l.is_loop => {
+                    return Err(ParseError::InvalidContinueLabel { span: label.span })
+                }
+                Some(_) => Some(label),
+            }
+        } else {
+            None
+        };
         self.auto_semicoclon_insertion()?;
         let span = self.end_span(start_span);
         match kind {
-            Type::Break => Ok(Statement::BreakStatement(Box::new(BreakStatement { span }))),
+            Type::Break => Ok(Statement::BreakStatement(Box::new(BreakStatement {
+                span,
+                label,
+            }))),
             Type::Continue => Ok(Statement::ContinueStatement(Box::new(ContinueStatement {
                 span,
+                label,
             }))),
             _ => unreachable!(),
         }
     }
 
+    /// Section 14.13 Labelled Statements
+    fn parse_labeled_statement(&mut self) -> Result<Statement<'a>> {
+        let span = self.start_span();
+        let label = self.parse_identifier()?;
+        if self.labels.iter().any(|l| l.name == label.name) {
+            return Err(ParseError::DuplicateLabel { span: label.span });
+        }
+        self.bump_any(); // bump `:`
+
+        let is_loop = matches!(self.cur_kind(), Type::Do | Type::While | Type::For);
+        if is_loop {
+            // `a: b: while (...)` labels the loop with `a` too
+            for outer in self.labels.iter_mut().rev() {
+                if !outer.chained {
+                    break;
+                }
+                outer.is_loop = true;
+            }
+        }
+        let chained = self.at_identifier() && self.peek_kind() == Type::Colon;
+        self.labels.push(Label {
+            name: label.name,
+            is_loop,
+            chained,
+        });
+        let body = self.parse_statement(StatementContext::Label);
+        self.labels.pop();
+        Ok(Statement::LabeledStatement(Box::new(LabeledStatement {
+            span: self.end_span(span),
+            label,
+            body: body?,
+        })))
+    }
+
     fn parse_do_while_statement(&mut self) -> Result<Statement<'a>> {
         let span = self.start_span();
         self.bump_any(); // advance `do`
@@ -76,26 +202,107 @@ impl<'a> Parser<'a> {
     fn parse_for_statement(&mut self) -> Result<Statement<'a>> {
         let span = self.start_span();
         self.bump_any(); // bump `for`
+        let r#await = self.ctx.has_await() && self.eat(Type::Await);
 
         self.expect(Type::LParen)?;
 
         // for (;..
-        if self.at(Type::Semicolon) {
+        if !r#await && self.at(Type::Semicolon) {
             return self.parse_for_loop(span, None);
         }
 
-        // for (let | for (const | for (var
-        if self.cur_kind().is_variable_declaration() {
-            return self.parse_variable_declaration_for_statement(span);
+        if !r#await && self.at(Type::RParen) {
+            return self.parse_for_loop(span, None);
         }
 
-        if self.at(Type::RParen) {
-            return self.parse_for_loop(span, None);
+        // [lookahead ≠ async of]: `for (async of` would be ambiguous with an async arrow function,
+        // `for (async of => {};;)`, so it cannot start a for-of loop
+        let async_of = (!r#await && self.at(Type::Async) && self.peek_kind() == Type::Of)
+            .then(|| self.cur_token_span());
+
+        // [~In]: an `in` here starts a for-in loop instead of a relational expression
+        let init_span = self.start_span();
+        let init = self.context(Context::empty(), Context::In, |p| {
+            // for (let | for (const | for (var
+            if p.cur_kind().is_variable_declaration() {
+                let decl = p.parse_variable_declaration(init_span)?;
+                Ok(ForStatementInit::VariableDeclaration(Box::new(decl)))
+            } else {
+                p.parse_expr().map(ForStatementInit::Expression)
+            }
+        })?;
+
+        match self.cur_kind() {
+            Type::Of => {
+                if let Some(span) = async_of {
+                    return Err(ParseError::UnexpectedToken {
+                        span,
+                        found: Type::Async,
+                    });
+                }
+                let left = self.parse_for_statement_left(init_span, init)?;
+                self.bump_any(); // bump `of`
+                let right = self.context(Context::In, Context::empty(), |p| {
+                    p.parse_assignment_expression_or_higher()
+                })?;
+                let body = self.parse_for_in_of_body()?;
+                Ok(Statement::ForOfStatement(Box::new(ForOfStatement {
+                    span: self.end_span(span),
+                    r#await,
+                    left,
+                    right,
+                    body,
+                })))
+            }
+            Type::In if !r#await => {
+                let left = self.parse_for_statement_left(init_span, init)?;
+                self.bump_any(); // bump `in`
+                let right = self.context(Context::In, Context::empty(), Self::parse_expr)?;
+                let body = self.parse_for_in_of_body()?;
+                Ok(Statement::ForInStatement(Box::new(ForInStatement {
+                    span: self.end_span(span),
+                    left,
+                    right,
+                    body,
+                })))
+            }
+            // `for await` is only allowed on for-of loops
+            found if r#await => Err(ParseError::ExpectedToken {
+                span: self.cur_token_span(),
+                expected: Type::Of,
+                found,
+            }),
+            _ => {
+                if let ForStatementInit::VariableDeclaration(decl) = &init {
+                    check_destructuring_initializers(decl)?;
+                }
+                self.parse_for_loop(span, Some(init))
+            }
         }
+    }
 
-        let init_expression = self.parse_expr()?;
+    /// The part of a for-in or for-of statement before `in` or `of`
+    fn parse_for_statement_left(
+        &mut self,
+        span: Span,
+        init: ForStatementInit<'a>,
+    ) -> Result<ForStatementLeft<'a>> {
+        match init {
+            ForStatementInit::VariableDeclaration(decl) => {
+                if decl.declarations.len() != 1 || decl.declarations[0].init.is_some() {
+                    return Err(ParseError::InvalidForInOfDeclaration { span: decl.span });
+                }
+                Ok(ForStatementLeft::VariableDeclaration(decl))
+            }
+            ForStatementInit::Expression(expr) => self
+                .reinterpret_as_assignment_target(span, expr)
+                .map(ForStatementLeft::AssignmentTarget),
+        }
+    }
 
-        self.parse_for_loop(span, Some(ForStatementInit::Expression(init_expression)))
+    fn parse_for_in_of_body(&mut self) -> Result<Statement<'a>> {
+        self.expect(Type::RParen)?;
+        self.parse_statement(StatementContext::For)
     }
 
     fn parse_for_loop(
@@ -126,23 +333,12 @@ impl<'a> Parser<'a> {
         })))
     }
 
-    fn parse_variable_declaration_for_statement(&mut self, span: Span) -> Result<Statement<'a>> {
-        let start_span = self.start_span();
-        let init_declaration = self.parse_variable_declaration(start_span)?;
-        let init = Some(ForStatementInit::VariableDeclaration(Box::new(
-            init_declaration,
-        )));
-        self.parse_for_loop(span, init)
-    }
-
     fn parse_return_statement(&mut self) -> Result<Statement<'a>> {
         let span = self.start_span();
         self.bump_any(); // advance `return`
         let argument = if self.eat(Type::Semicolon) || self.can_insert_semicolon() {
             None
         } else {
-            // TODO: `In` context
-            // let expr = self.context(Context::In, Context::empty(), ParserImpl::parse_expr)?;
             let expr = self.parse_expr()?;
             self.auto_semicoclon_insertion()?;
             Some(expr)
@@ -153,6 +349,70 @@ impl<'a> Parser<'a> {
         })))
     }
 
+    /// Section 14.14 Throw Statement
+    fn parse_throw_statement(&mut self) -> Result<Statement<'a>> {
+        let span = self.start_span();
+        let keyword_span = self.cur_token_span();
+        self.bump_any(); // advance `throw`
+
+        // [no LineTerminator here], otherwise `throw` would have no argument
+        if self.cur_token().is_on_new_line {
+            return Err(ParseError::NewlineAfterThrow { span: keyword_span });
+        }
+        let argument = self.parse_expr()?;
+        self.auto_semicoclon_insertion()?;
+        Ok(Statement::ThrowStatement(Box::new(ThrowStatement {
+            span: self.end_span(span),
+            argument,
+        })))
+    }
+
+    /// Section 14.15 Try Statement
+    fn parse_try_statement(&mut self) -> Result<Statement<'a>> {
+        let span = self.start_span();
+        self.bump_any(); // advance `try`
+        let block = self.parse_block()?;
+        let handler = self
+            .at(Type::Catch)
+            .then(|| self.parse_catch_clause())
+            .transpose()?;
+        let finalizer = self
+            .eat(Type::Finally)
+            .then(|| self.parse_block())
+            .transpose()?;
+        if handler.is_none() && finalizer.is_none() {
+            return Err(ParseError::ExpectedToken {
+                span: self.cur_token_span(),
+                expected: Type::Catch,
+                found: self.cur_kind(),
+            });
+        }
+        Ok(Statement::TryStatement(Box::new(TryStatement {
+            span: self.end_span(span),
+            block,
+            handler,
+            finalizer,
+        })))
+    }
+
+    fn parse_catch_clause(&mut self) -> Result<Box<CatchClause<'a>>> {
+        let span = self.start_span();
+        self.bump_any(); // advance `catch`
+        let param = if self.eat(Type::LParen) {
+            let param = self.parse_binding_pattern()?;
+            self.expect(Type::RParen)?;
+            Some(param)
+        } else {
+            None
+        };
+        let body = self.parse_block()?;
+        Ok(Box::new(CatchClause {
+            span: self.end_span(span),
+            param,
+            body,
+        }))
+    }
+
     fn parse_empty_statement(&mut self) -> Statement<'a> {
         let span = self.start_span();
         self.bump_any(); // bump `;`
@@ -173,20 +433,24 @@ impl<'a> Parser<'a> {
         )))
     }
 
-    fn parse_block_statement(&mut self) -> Result<Statement<'a>> {
+    fn parse_block(&mut self) -> Result<Box<BlockStatement<'a>>> {
         let span = self.start_span();
         self.expect(Type::LCurly)?;
         let mut body = vec![];
         while !self.at(Type::RCurly) && !self.at(Type::EOF) {
-            let stmt = self.parse_statement(StatementContext::StatementList)?;
+            let stmt = self.parse_statement_list_item(StatementContext::StatementList)?;
             body.push(stmt);
         }
         self.expect(Type::RCurly)?;
 
-        Ok(Statement::BlockStatement(Box::new(BlockStatement {
+        Ok(Box::new(BlockStatement {
             span: self.end_span(span),
             body,
-        })))
+        }))
+    }
+
+    fn parse_block_statement(&mut self) -> Result<Statement<'a>> {
+        self.parse_block().map(Statement::BlockStatement)
     }
 
     fn parse_if_statement(&mut self) -> Result<Statement<'a>> {
@@ -239,7 +503,7 @@ impl<'a> Parser<'a> {
             self.cur_kind(),
             Type::Case | Type::Default | Type::RCurly | Type::EOF
         ) {
-            let stmt = self.parse_statement(StatementContext::StatementList)?;
+            let stmt = self.parse_statement_list_item(StatementContext::StatementList)?;
             consequent.push(stmt);
         }
         Ok(Some(SwitchCase {
@@ -285,16 +549,29 @@ impl<'a> Parser<'a> {
         self.bump_any();
         // self.bump(Type::Var);
 
-        let id = self.parse_identifier()?;
+        let mut declarations = vec![self.parse_variable_declarator()?];
+        while self.eat(Type::Comma) {
+            declarations.push(self.parse_variable_declarator()?);
+        }
+
+        Ok(VariableDeclaration {
+            span: self.end_span(start_span),
+            kind,
+            declarations,
+        })
+    }
+
+    fn parse_variable_declarator(&mut self) -> Result<VariableDeclarator<'a>> {
+        let span = self.start_span();
+        let id = self.parse_binding_pattern()?;
 
         let init = self
             .eat(Type::Eq)
             .then(|| self.parse_assignment_expression_or_higher())
             .transpose()?;
 
-        Ok(VariableDeclaration {
-            span: self.end_span(start_span),
-            kind,
+        Ok(VariableDeclarator {
+            span: self.end_span(span),
             id,
             init,
         })
@@ -304,6 +581,22 @@ impl<'a> Parser<'a> {
     fn parse_variable_statement(&mut self, stmt_ctx: StatementContext) -> Result<Statement<'a>> {
         let start_span = self.start_span();
         let decl = self.parse_variable_declaration(start_span)?;
+        check_destructuring_initializers(&decl)?;
+        self.auto_semicoclon_insertion()?;
         Ok(Statement::VariableDeclarationStatement(Box::new(decl)))
     }
 }
+
+/// Patterns have to be initialized, except in the head of a for-in or for-of loop
+fn check_destructuring_initializers(decl: &VariableDeclaration) -> Result<()> {
+    for declarator in &decl.declarations {
+        if declarator.init.is_none()
+            && !matches!(declarator.id, BindingPattern::BindingIdentifier(_))
+        {
+            return Err(ParseError::MissingDestructuringInitializer {
+                span: declarator.span,
+            });
+        }
+    }
+    Ok(())
+}
@@ -8,6 +8,7 @@ pub trait TokenTypeUtil {
     fn is_logical_operator(self) -> bool;
     fn is_binary_operator(self) -> bool;
     fn is_unary_operator(self) -> bool;
+    fn is_update_operator(self) -> bool;
     fn is_literal(self) -> bool;
     fn is_reserved_keyword(self) -> bool;
     fn is_identifier(self) -> bool;
@@ -18,6 +19,7 @@ pub trait TokenTypeUtil {
     fn is_all_keyword(self) -> bool;
     fn is_variable_declaration(self) -> bool;
     fn is_assignment_operator(self) -> bool;
+    fn is_statement_keyword(self) -> bool;
 }
 
 impl TokenTypeUtil for Type {
@@ -30,17 +32,20 @@ impl TokenTypeUtil for Type {
     }
 
     fn is_logical_operator(self) -> bool {
-        matches!(self, Pipe2 | Amp2)
+        matches!(self, Pipe2 | Amp2 | Question2)
     }
     #[rustfmt::skip]
     fn is_binary_operator(self) -> bool {
         matches!(self, Eq2 | Neq | Eq3 | Neq2 | LAngle | LtEq | RAngle | GtEq | ShiftLeft | ShiftRight
             | ShiftRight3 | Plus | Minus | Star | Slash | Percent | Pipe | Caret | Amp | In
-            | Instanceof)
+            | Instanceof | Star2)
     }
     fn is_unary_operator(self) -> bool {
         matches!(self, Minus | Plus | Bang | Tilde | Typeof | Void | Delete)
     }
+    fn is_update_operator(self) -> bool {
+        matches!(self, Plus2 | Minus2)
+    }
     fn is_literal(self) -> bool {
         matches!(self, Null | True | False | Str) || self.is_number()
     }
@@ -88,6 +93,13 @@ impl TokenTypeUtil for Type {
     }
     #[rustfmt::skip]
     fn is_assignment_operator(self) -> bool {
-        matches!(self, Eq | PlusEq | MinusEq | StarEq | SlashEq | PercentEq | ShiftLeftEq | ShiftRightEq | ShiftRight3Eq | AmpEq | CaretEq | PipeEq)
+        matches!(self, Eq | PlusEq | MinusEq | StarEq | SlashEq | PercentEq | ShiftLeftEq | ShiftRightEq | ShiftRight3Eq | AmpEq | CaretEq | PipeEq
+            | Question2Eq | Amp2Eq | Pipe2Eq | Star2Eq)
+    }
+
+    #[rustfmt::skip]
+    fn is_statement_keyword(self) -> bool {
+        matches!(self, Break | Class | Const | Continue | Debugger | Do | For | Function | If | Let
+            | Return | Switch | Throw | Try | Var | While | With)
     }
 }
@@ -1,5 +1,7 @@
 use serde::Serialize;
 
+use super::precedence::Precedence;
+
 // #![allow(non_snake_case)]
 
 /// Operators that may be used in assignment epxressions.
@@ -138,3 +140,125 @@ pub enum UpdateOperator {
     #[serde(rename = "--")]
     Decrement = 1,
 }
+
+impl AssignmentOperator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Assign => "=",
+            Self::Addition => "+=",
+            Self::Subtraction => "-=",
+            Self::Multiplication => "*=",
+            Self::Division => "/=",
+            Self::Remainder => "%=",
+            Self::ShiftLeft => "<<=",
+            Self::ShiftRight => ">>=",
+            Self::ShiftRightZeroFill => ">>>=",
+            Self::BitwiseOR => "|=",
+            Self::BitwiseXOR => "^=",
+            Self::BitwiseAnd => "&=",
+            Self::LogicalAnd => "&&=",
+            Self::LogicalOr => "||=",
+            Self::LogicalNullish => "??=",
+            Self::Exponential => "**=",
+        }
+    }
+}
+
+impl BinaryOperator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Equality => "==",
+            Self::Inequality => "!=",
+            Self::StrictEquality => "===",
+            Self::StrictInequality => "!==",
+            Self::LessThan => "<",
+            Self::LessEqualThan => "<=",
+            Self::GreaterThan => ">",
+            Self::GreaterEqualThan => ">=",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::ShiftRightZeroFill => ">>>",
+            Self::Addition => "+",
+            Self::Subtraction => "-",
+            Self::Multiplication => "*",
+            Self::Division => "/",
+            Self::Remainder => "%",
+            Self::BitwiseOR => "|",
+            Self::BitwiseXOR => "^",
+            Self::BitwiseAnd => "&",
+            Self::In => "in",
+            Self::Instanceof => "instanceof",
+            Self::Exponential => "**",
+        }
+    }
+
+    pub fn precedence(self) -> Precedence {
+        match self {
+            Self::Equality | Self::Inequality | Self::StrictEquality | Self::StrictInequality => {
+                Precedence::Equals
+            }
+            Self::LessThan
+            | Self::LessEqualThan
+            | Self::GreaterThan
+            | Self::GreaterEqualThan
+            | Self::In
+            | Self::Instanceof => Precedence::Compare,
+            Self::ShiftLeft | Self::ShiftRight | Self::ShiftRightZeroFill => Precedence::Shift,
+            Self::Addition | Self::Subtraction => Precedence::Add,
+            Self::Multiplication | Self::Division | Self::Remainder => Precedence::Multiply,
+            Self::BitwiseOR => Precedence::BitwiseOr,
+            Self::BitwiseXOR => Precedence::BitwiseXor,
+            Self::BitwiseAnd => Precedence::BitwiseAnd,
+            Self::Exponential => Precedence::Exponentiation,
+        }
+    }
+
+    pub fn is_keyword(self) -> bool {
+        matches!(self, Self::In | Self::Instanceof)
+    }
+}
+
+impl LogicalOperator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Or => "||",
+            Self::And => "&&",
+            Self::Coalesce => "??",
+        }
+    }
+
+    pub fn precedence(self) -> Precedence {
+        match self {
+            Self::Or => Precedence::LogicalOr,
+            Self::And => Precedence::LogicalAnd,
+            Self::Coalesce => Precedence::NullishCoalescing,
+        }
+    }
+}
+
+impl UnaryOperator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UnaryNegation => "-",
+            Self::UnaryPlus => "+",
+            Self::LogicalNot => "!",
+            Self::BitwiseNot => "~",
+            Self::Typeof => "typeof",
+            Self::Void => "void",
+            Self::Delete => "delete",
+        }
+    }
+
+    pub fn is_keyword(self) -> bool {
+        matches!(self, Self::Typeof | Self::Void | Self::Delete)
+    }
+}
+
+impl UpdateOperator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Increment => "++",
+            Self::Decrement => "--",
+        }
+    }
+}